@@ -1,4 +1,6 @@
-advent_of_code::solution!(1);
+use advent_of_code::template::property::Rng;
+
+advent_of_code::solution!(1, generate = generate_input);
 
 fn char_to_floor(c: char) -> i32 {
     match c {
@@ -26,9 +28,17 @@ pub fn part_two(input: &str) -> Option<i32> {
     None
 }
 
+/// A random walk up and down the floors.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    (0..size.unwrap_or(7000))
+        .map(|_| *rng.pick(&['(', ')']))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::property::{self, Property};
 
     #[test]
     fn test_part_one() {
@@ -41,4 +51,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1));
     }
+
+    #[test]
+    fn floor_is_balance() {
+        Property::new(DAY, "floor_is_balance").check(&property::string_of("()", 500), |input| {
+            let balance = input.matches('(').count() as i32 - input.matches(')').count() as i32;
+            part_one(input) == Some(balance)
+        });
+    }
 }
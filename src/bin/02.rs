@@ -1,14 +1,15 @@
 use std::cmp::min;
 
+use advent_of_code::parse::{parse_lines, ParseError};
+use advent_of_code::template::property::Rng;
 use nom::{
     bytes::complete::tag,
-    character::{self, complete::newline},
-    multi::separated_list1,
+    character,
     sequence::{preceded, tuple},
     IResult,
 };
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, generate = generate_input);
 
 type Dimensions = (u32, u32, u32);
 
@@ -20,10 +21,8 @@ fn parse_size(input: &str) -> IResult<&str, Dimensions> {
     ))(input)
 }
 
-fn parse_sizes(input: &str) -> Vec<Dimensions> {
-    let (_, sizes) = separated_list1(newline, parse_size)(input).expect("should parse");
-
-    sizes
+fn parse_sizes(input: &str) -> Result<Vec<Dimensions>, ParseError> {
+    parse_lines(input, parse_size)
 }
 
 fn calculate_required_paper(size: Dimensions) -> u32 {
@@ -51,16 +50,27 @@ fn calculate_required_ribbon(size: Dimensions) -> u32 {
     ribbon_length + bow_length
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let sizes = parse_sizes(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let sizes = parse_sizes(input)?;
 
-    Some(sizes.into_iter().map(calculate_required_paper).sum())
+    Ok(sizes.into_iter().map(calculate_required_paper).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let sizes = parse_sizes(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let sizes = parse_sizes(input)?;
+
+    Ok(sizes.into_iter().map(calculate_required_ribbon).sum())
+}
 
-    Some(sizes.into_iter().map(calculate_required_ribbon).sum())
+/// Presents with sides between 1 and 30 feet. The size is the number of presents.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    (0..size.unwrap_or(1000))
+        .map(|_| {
+            let mut side = || rng.below(30) + 1;
+            format!("{}x{}x{}", side(), side(), side())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -70,12 +80,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(101));
+        assert_eq!(result, Ok(101));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(48));
+        assert_eq!(result, Ok(48));
     }
 }
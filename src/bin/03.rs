@@ -1,6 +1,12 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
 
-advent_of_code::solution!(3);
+use advent_of_code::template::{explain, property::Rng};
+use itertools::Itertools;
+
+advent_of_code::solution!(3, generate = generate_input);
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct Point {
@@ -14,69 +20,221 @@ impl Point {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut location = Point::new(0, 0);
-    let mut deliveries = HashSet::new();
-    deliveries.insert(location);
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
 
-    for c in input.chars() {
-        match c {
-            '^' => location.y += 1,
-            'v' => location.y -= 1,
-            '>' => location.x += 1,
-            '<' => location.x -= 1,
-            _ => panic!(),
-        }
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct DirectionError {
+    position: usize,
+    found: char,
+}
 
-        deliveries.insert(location);
+impl Display for DirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unexpected {:?} at position {}, expected one of '^', 'v', '>' or '<'",
+            self.found, self.position
+        )
     }
+}
 
-    Some(deliveries.len() as u32)
+// NOTE: the answers only need the number of houses, the other statistics are shown with `--explain`.
+#[derive(Debug)]
+struct DeliveryReport {
+    /// Presents delivered to each house.
+    visits: HashMap<Point, u32>,
+    /// Distinct houses visited by each agent, in turn order.
+    agent_houses: Vec<usize>,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut move_santa = true;
-    let mut santa_location = Point::new(0, 0);
-    let mut robot_location = Point::new(0, 0);
+impl DeliveryReport {
+    fn houses_visited(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// The houses that received the most presents, along with that number of presents.
+    fn most_visited(&self) -> (u32, Vec<Point>) {
+        let max = self.visits.values().copied().max().unwrap_or(0);
+        let houses = self
+            .visits
+            .iter()
+            .filter(|(_, &count)| count == max)
+            .map(|(point, _)| *point)
+            .collect();
+
+        (max, houses)
+    }
+
+    /// The smallest rectangle, given as its bottom-left and top-right corner, containing every visited house.
+    fn bounding_box(&self) -> (Point, Point) {
+        self.visits
+            .keys()
+            .fold((Point::new(0, 0), Point::new(0, 0)), |(min, max), point| {
+                (
+                    Point::new(min.x.min(point.x), min.y.min(point.y)),
+                    Point::new(max.x.max(point.x), max.y.max(point.y)),
+                )
+            })
+    }
 
-    let mut deliveries = HashSet::new();
-    deliveries.insert(santa_location);
+    fn explain(&self) {
+        explain::detail("most visited", || {
+            let (presents, houses) = self.most_visited();
+            format!("{} with {presents} presents", houses.iter().join(", "))
+        });
+        explain::detail("bounding box", || {
+            let (min, max) = self.bounding_box();
+            format!("{min} to {max}")
+        });
+        explain::detail("houses per agent", || self.agent_houses.iter().join(", "));
+    }
+}
 
-    for c in input.chars() {
-        let location = if move_santa {
-            &mut santa_location
-        } else {
-            &mut robot_location
-        };
+/// Simulates `agents` deliverers taking turns following the directions, all starting at the same house.
+/// Trailing whitespace is ignored, any other unknown character is an error.
+///
+/// # Panics
+/// Panics if there are no agents.
+fn deliver(input: &str, agents: usize) -> Result<DeliveryReport, DirectionError> {
+    assert!(
+        agents > 0,
+        "at least one agent is needed to deliver presents"
+    );
+
+    let start = Point::new(0, 0);
+    let mut locations = vec![start; agents];
+    let mut agent_visits = vec![HashSet::from([start]); agents];
+    let mut visits = HashMap::from([(start, agents as u32)]);
+
+    for (position, c) in input.trim_end().chars().enumerate() {
+        let agent = position % agents;
+        let location = &mut locations[agent];
 
         match c {
             '^' => location.y += 1,
             'v' => location.y -= 1,
             '>' => location.x += 1,
             '<' => location.x -= 1,
-            _ => panic!(),
+            found => return Err(DirectionError { position, found }),
         }
 
-        deliveries.insert(*location);
-        move_santa = !move_santa;
+        *visits.entry(*location).or_insert(0) += 1;
+        agent_visits[agent].insert(*location);
     }
 
-    Some(deliveries.len() as u32)
+    Ok(DeliveryReport {
+        visits,
+        agent_houses: agent_visits.iter().map(HashSet::len).collect(),
+    })
+}
+
+pub fn part_one(input: &str) -> Result<u32, DirectionError> {
+    let report = deliver(input, 1)?;
+    report.explain();
+
+    Ok(report.houses_visited() as u32)
+}
+
+pub fn part_two(input: &str) -> Result<u32, DirectionError> {
+    let report = deliver(input, 2)?;
+    report.explain();
+
+    Ok(report.houses_visited() as u32)
+}
+
+/// Random directions. The size is the number of moves.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    (0..size.unwrap_or(8192))
+        .map(|_| *rng.pick(&['^', 'v', '>', '<']))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::property::{self, Property};
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result, Ok(11));
+    }
+
+    #[test]
+    fn test_report() {
+        let report = deliver("^>v<\n", 1).unwrap();
+        assert_eq!(report.houses_visited(), 4);
+        assert_eq!(report.most_visited(), (2, vec![Point::new(0, 0)]));
+        assert_eq!(report.bounding_box(), (Point::new(0, 0), Point::new(1, 1)));
+        assert_eq!(report.agent_houses, vec![4]);
+    }
+
+    #[test]
+    fn test_many_agents() {
+        let report = deliver("^^^vvv", 3).unwrap();
+        assert_eq!(report.houses_visited(), 2);
+        assert_eq!(report.most_visited(), (6, vec![Point::new(0, 0)]));
+        assert_eq!(report.agent_houses, vec![2, 2, 2]);
+
+        let report = deliver("^>v<", 4).unwrap();
+        assert_eq!(report.houses_visited(), 5);
+        assert_eq!(
+            report.bounding_box(),
+            (Point::new(-1, -1), Point::new(1, 1))
+        );
+        assert_eq!(report.agent_houses, vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one agent")]
+    fn test_no_agents() {
+        let _ = deliver("^>", 0);
+    }
+
+    #[test]
+    fn test_stray_characters() {
+        assert_eq!(
+            deliver("^^x>", 2).unwrap_err(),
+            DirectionError {
+                position: 2,
+                found: 'x'
+            }
+        );
+        assert_eq!(
+            deliver("^\n>", 1).unwrap_err(),
+            DirectionError {
+                position: 1,
+                found: '\n'
+            }
+        );
+        assert_eq!(
+            part_one("^^x>").unwrap_err().to_string(),
+            "unexpected 'x' at position 2, expected one of '^', 'v', '>' or '<'"
+        );
+    }
+
+    #[test]
+    fn houses_are_bounded_by_moves() {
+        let moves = property::from_generator(generate_input, 500);
+
+        Property::new(DAY, "houses_are_bounded_by_moves").check_parts(
+            &moves,
+            part_one,
+            part_two,
+            |input, one, two| {
+                let bound = input.len() as u32 + 1;
+                one.is_ok_and(|houses| houses <= bound) && two.is_ok_and(|houses| houses <= bound)
+            },
+        );
     }
 }
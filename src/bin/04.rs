@@ -1,6 +1,7 @@
+use advent_of_code::template::property::Rng;
 use md5::{Digest, Md5};
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, generate = generate_input);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut key_suffix = 1;
@@ -42,21 +43,27 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(key_suffix)
 }
 
+/// A secret key of 8 lowercase letters. The size is ignored.
+fn generate_input(rng: &mut Rng, _size: Option<usize>) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    (0..8).map(|_| *rng.pick(&letters)).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
     #[test]
-    fn test_part_one() {
-        // let result = part_one(&advent_of_code::template::read_file("examples", DAY)); Slow test
-        let result = Some(1048970);
+    #[ignore = "slow"]
+    fn slow_test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1048970));
     }
 
     #[test]
-    fn test_part_two() {
-        // let result = part_two(&advent_of_code::template::read_file("examples", DAY)); // Slow test
-        let result = Some(5714438);
+    #[ignore = "slow"]
+    fn slow_test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5714438));
     }
 }
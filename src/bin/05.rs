@@ -1,55 +1,49 @@
-use itertools::Itertools;
+use advent_of_code::rules::{forbidden, letter_repeat, min_vowels, repeated_pair, Rule};
+use advent_of_code::template::property::Rng;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, generate = generate_input);
 
-fn num_vowels(s: &str) -> u32 {
-    s.chars()
-        .filter(|c| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u'))
-        .count() as u32
+fn nice_rules1() -> impl Rule {
+    min_vowels(3)
+        .and(letter_repeat(0))
+        .and(forbidden(["ab", "cd", "pq", "xy"]))
 }
 
-fn has_double_letters(s: &str) -> bool {
-    s.chars().tuple_windows().any(|(c1, c2)| c1 == c2)
+fn nice_rules2() -> impl Rule {
+    repeated_pair().and(letter_repeat(1))
 }
 
-fn no_disallowed_strings(s: &str) -> bool {
-    !(s.contains("ab") || s.contains("cd") || s.contains("pq") || s.contains("xy"))
-}
-
-fn is_nice1(s: &str) -> bool {
-    num_vowels(s) >= 3 && has_double_letters(s) && no_disallowed_strings(s)
-}
-
-fn repeated_letter_pair(s: &str) -> bool {
-    let chars: Vec<char> = s.chars().collect();
-
-    for index in 0..chars.len() - 1 {
-        let search = &chars[index..index + 2];
-
-        for search_index in index + 2..chars.len() - 1 {
-            if chars[search_index..search_index + 2] == *search {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-fn letter_repeats_after(s: &str) -> bool {
-    s.chars().tuple_windows().any(|(c1, _, c3)| c1 == c3)
-}
-
-fn is_nice2(s: &str) -> bool {
-    repeated_letter_pair(s) && letter_repeats_after(s)
+fn count_nice(input: &str, rules: impl Rule) -> u32 {
+    input.lines().filter(|s| rules.is_satisfied(s)).count() as u32
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    Some(input.lines().filter(|s| is_nice1(s)).count() as u32)
+    Some(count_nice(input, nice_rules1()))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(input.lines().filter(|s| is_nice2(s)).count() as u32)
+    Some(count_nice(input, nice_rules2()))
+}
+
+/// Strings of 16 lowercase letters, with some doubled letters so that not all of them are naughty.
+/// The size is the number of strings.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+
+    (0..size.unwrap_or(1000))
+        .map(|_| {
+            let mut s = String::new();
+            while s.len() < 16 {
+                let letter = *rng.pick(&letters);
+                s.push(letter);
+                if s.len() < 16 && rng.below(8) == 0 {
+                    s.push(letter);
+                }
+            }
+            s
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -71,4 +65,20 @@ mod tests {
         ));
         assert_eq!(result, Some(2));
     }
+
+    #[test]
+    fn test_failed_rules() {
+        assert_eq!(
+            nice_rules1().check("haegwjzuvuyypxyu").unwrap_err().rule,
+            r#"none of ["ab", "cd", "pq", "xy"] (found "xy" at 13)"#
+        );
+        assert_eq!(
+            nice_rules2().check("uurcxstgmygtbstg").unwrap_err().rule,
+            "a letter repeating after a gap of 1"
+        );
+        assert_eq!(
+            nice_rules2().check("ieodomkazucvgmuy").unwrap_err().rule,
+            "a non-overlapping repeated pair"
+        );
+    }
 }
@@ -1,15 +1,12 @@
+use std::fmt::Display;
+
+use advent_of_code::parse::{parse_lines, ParseError};
+use advent_of_code::template::property::Rng;
 use grid_2d::{Coord, Grid, Size};
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::{self, complete::newline},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+use nom::{branch::alt, bytes::complete::tag, character, sequence::separated_pair, IResult};
 use num::Saturating;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, params { size: u32 = 1000 }, generate = generate_input);
 
 #[derive(Debug)]
 enum Action {
@@ -63,19 +60,67 @@ fn parse_command(input: &str) -> IResult<&str, Command> {
     Ok((input, Command::new(action.into(), start, end)))
 }
 
-fn parse_commands(input: &str) -> IResult<&str, Vec<Command>> {
-    separated_list1(newline, parse_command)(input)
+fn parse_input(input: &str) -> Result<Vec<Command>, ParseError> {
+    parse_lines(input, parse_command)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LightsError {
+    Parse(ParseError),
+    /// A corner of the instruction on `line` that lies outside of the grid.
+    OutOfBounds {
+        line: usize,
+        corner: Coord,
+        size: u32,
+    },
+}
+
+impl Display for LightsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LightsError::Parse(err) => err.fmt(f),
+            LightsError::OutOfBounds { line, corner, size } => write!(
+                f,
+                "line {line}: {},{} is outside of the {size}x{size} grid",
+                corner.x, corner.y
+            ),
+        }
+    }
+}
+
+impl From<ParseError> for LightsError {
+    fn from(err: ParseError) -> Self {
+        LightsError::Parse(err)
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Command> {
-    let (_, commands) = parse_commands(input).expect("should parse");
+/// Parses the instructions and checks that they stay within a grid of `size`x`size` lights.
+fn parse_instructions(input: &str, size: u32) -> Result<Vec<Command>, LightsError> {
+    let commands = parse_input(input)?;
+    let is_inside = |coord: Coord| {
+        u32::try_from(coord.x).is_ok_and(|x| x < size)
+            && u32::try_from(coord.y).is_ok_and(|y| y < size)
+    };
+
+    for (index, command) in commands.iter().enumerate() {
+        if let Some(corner) = [command.start_point, command.end_point]
+            .into_iter()
+            .find(|corner| !is_inside(*corner))
+        {
+            return Err(LightsError::OutOfBounds {
+                line: index + 1,
+                corner,
+                size,
+            });
+        }
+    }
 
-    commands
+    Ok(commands)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let commands = parse_input(input);
-    let mut grid = Grid::new_copy(Size::new(1000, 1000), false);
+pub fn part_one(input: &str, params: &Params) -> Result<u32, LightsError> {
+    let commands = parse_instructions(input, params.size)?;
+    let mut grid = Grid::new_copy(Size::new(params.size, params.size), false);
 
     for command in commands {
         for y in command.start_point.y..=command.end_point.y {
@@ -91,12 +136,12 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(grid.iter().filter(|cell| **cell).count() as u32)
+    Ok(grid.iter().filter(|cell| **cell).count() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let commands = parse_input(input);
-    let mut grid = Grid::new_copy(Size::new(1000, 1000), 0);
+pub fn part_two(input: &str, params: &Params) -> Result<u32, LightsError> {
+    let commands = parse_instructions(input, params.size)?;
+    let mut grid = Grid::new_copy(Size::new(params.size, params.size), 0);
 
     for command in commands {
         for y in command.start_point.y..=command.end_point.y {
@@ -112,7 +157,29 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(grid.iter().sum())
+    Ok(grid.iter().sum())
+}
+
+/// Instructions for a grid of the default size. The size is the number of instructions.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let bounds = Params::default().size as usize;
+
+    (0..size.unwrap_or(300))
+        .map(|_| {
+            let action = *rng.pick(&["turn on", "turn off", "toggle"]);
+            let (x1, x2) = (rng.below(bounds), rng.below(bounds));
+            let (y1, y2) = (rng.below(bounds), rng.below(bounds));
+
+            format!(
+                "{action} {},{} through {},{}",
+                x1.min(x2),
+                y1.min(y2),
+                x1.max(x2),
+                y1.max(y2)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -121,13 +188,43 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(998_996));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(86));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1_001_996));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(116));
+    }
+
+    #[test]
+    fn test_full_grid() {
+        let input = "turn on 0,0 through 999,999\ntoggle 0,0 through 999,0\nturn off 499,499 through 500,500";
+        assert_eq!(part_one(input, &Params::default()), Ok(998_996));
+        assert_eq!(part_two(input, &Params::default()), Ok(1_001_996));
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let input = "turn on 0,0 through 9,9\ntoggle 0,0 through 10,0";
+        let error = LightsError::OutOfBounds {
+            line: 2,
+            corner: Coord::new(10, 0),
+            size: 10,
+        };
+
+        assert_eq!(part_one(input, &Params::example()), Err(error.clone()));
+        assert_eq!(part_two(input, &Params::example()), Err(error.clone()));
+        assert_eq!(
+            error.to_string(),
+            "line 2: 10,0 is outside of the 10x10 grid"
+        );
     }
 }
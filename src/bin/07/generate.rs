@@ -0,0 +1,52 @@
+//! Generates synthetic circuits for `cargo generate-input`.
+
+use advent_of_code::template::property::Rng;
+
+/// Two-letter wire names, so they never collide with `a` and `b`.
+fn wire_name(index: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    format!("{}{}", letters[index / 26 % 26], letters[index % 26])
+}
+
+/// A circuit of gates, in which every wire only depends on wires created before it. Wire `b` carries a signal
+/// and wire `a` is the last one, as in the puzzle. The size is the number of wires, at most 676.
+pub fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let count = size.unwrap_or(339).clamp(2, 676);
+    let names: Vec<String> = (0..count)
+        .map(|index| match index {
+            0 => "b".to_string(),
+            index if index == count - 1 => "a".to_string(),
+            index => wire_name(index),
+        })
+        .collect();
+
+    let mut lines: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            if index == 0 || rng.below(20) == 0 {
+                return format!("{} -> {name}", rng.below(65536));
+            }
+
+            let (gate, shift) = (rng.below(7), rng.below(15) + 1);
+            let mut wire = || names[rng.below(index)].clone();
+            let source = match gate {
+                0 => format!("{} AND {}", wire(), wire()),
+                1 => format!("{} OR {}", wire(), wire()),
+                2 => format!("NOT {}", wire()),
+                3 => format!("{} LSHIFT {shift}", wire()),
+                4 => format!("{} RSHIFT {shift}", wire()),
+                5 => format!("1 AND {}", wire()),
+                _ => wire(),
+            };
+            format!("{source} -> {name}")
+        })
+        .collect();
+
+    // the puzzle lists connections in no particular order.
+    for index in (1..lines.len()).rev() {
+        lines.swap(index, rng.below(index + 1));
+    }
+
+    lines.join("\n")
+}
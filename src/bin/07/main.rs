@@ -0,0 +1,55 @@
+mod generate;
+mod parse;
+mod solve;
+
+use generate::generate_input;
+use parse::own_input;
+use solve::{part_one, part_two};
+
+advent_of_code::solution!(
+    7,
+    owned = own_input,
+    generate = generate_input,
+    scaling = [16, 32, 64, 128, 256, 512],
+    params {
+        wire: String = "a",
+        override_wire: String = "b",
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code::template::property::{self, Property};
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(
+            advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(492));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(
+            advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(1968));
+    }
+
+    #[test]
+    fn generated_circuits_resolve() {
+        let circuits = property::from_generator(generate_input, 200);
+        let params = Params::default();
+
+        Property::new(DAY, "generated_circuits_resolve").check_parts(
+            &circuits,
+            |input| part_one(input.to_string(), &params),
+            |input| part_two(input.to_string(), &params),
+            |_, one, two| one.is_ok() && two.is_ok(),
+        );
+    }
+}
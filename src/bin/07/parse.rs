@@ -0,0 +1,109 @@
+//! Parses the instructions for assembling the circuit.
+
+use std::collections::HashMap;
+
+use advent_of_code::parse::{parse_lines, ParseError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, not_line_ending},
+    sequence::separated_pair,
+    IResult,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Source {
+    And(Input, Input),
+    Or(Input, Input),
+    Not(Input),
+    LShift(Input, Input),
+    RShift(Input, Input),
+    Signal(Input),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Input {
+    Constant(u16),
+    Wire(String),
+}
+
+pub type Connections = HashMap<String, Source>;
+
+fn parse_source_input(input: &str) -> IResult<&str, Input> {
+    let (input, source_input) = alt((alpha1, digit1))(input)?;
+
+    if source_input.chars().next().unwrap().is_alphabetic() {
+        Ok((input, Input::Wire(source_input.to_string())))
+    } else {
+        Ok((input, Input::Constant(source_input.parse().unwrap())))
+    }
+}
+
+fn parse_and(input: &str) -> IResult<&str, Source> {
+    let (input, (left, right)) =
+        separated_pair(parse_source_input, tag(" AND "), parse_source_input)(input)?;
+
+    Ok((input, Source::And(left, right)))
+}
+
+fn parse_or(input: &str) -> IResult<&str, Source> {
+    let (input, (left, right)) =
+        separated_pair(parse_source_input, tag(" OR "), parse_source_input)(input)?;
+
+    Ok((input, Source::Or(left, right)))
+}
+
+fn parse_not(input: &str) -> IResult<&str, Source> {
+    let (input, _) = tag("NOT ")(input)?;
+    let (input, right) = parse_source_input(input)?;
+
+    Ok((input, Source::Not(right)))
+}
+
+fn parse_lshift(input: &str) -> IResult<&str, Source> {
+    let (input, (left, right)) =
+        separated_pair(parse_source_input, tag(" LSHIFT "), parse_source_input)(input)?;
+
+    Ok((input, Source::LShift(left, right)))
+}
+
+fn parse_rshift(input: &str) -> IResult<&str, Source> {
+    let (input, (left, right)) =
+        separated_pair(parse_source_input, tag(" RSHIFT "), parse_source_input)(input)?;
+
+    Ok((input, Source::RShift(left, right)))
+}
+
+fn parse_signal(input: &str) -> IResult<&str, Source> {
+    let (input, source_input) = parse_source_input(input)?;
+
+    Ok((input, Source::Signal(source_input)))
+}
+
+fn parse_source(input: &str) -> IResult<&str, Source> {
+    alt((
+        parse_and,
+        parse_or,
+        parse_not,
+        parse_lshift,
+        parse_rshift,
+        parse_signal,
+    ))(input)
+}
+
+fn parse_connection(input: &str) -> IResult<&str, (String, Source)> {
+    let (input, source) = parse_source(input)?;
+    let (input, _) = tag(" -> ")(input)?;
+    let (input, destination) = not_line_ending(input)?;
+
+    Ok((input, (destination.to_string(), source)))
+}
+
+/// Setup of the parts, which take the input as an owned copy. Parsing stays in the parts, so that it is timed.
+pub fn own_input(input: &str) -> String {
+    input.to_string()
+}
+
+pub fn parse_input(input: &str) -> Result<Connections, ParseError> {
+    Ok(parse_lines(input, parse_connection)?.into_iter().collect())
+}
@@ -0,0 +1,94 @@
+//! Resolves the signals on the wires of a circuit.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use advent_of_code::parse::ParseError;
+
+use crate::parse::{parse_input, Connections, Input, Source};
+use crate::Params;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    Parse(ParseError),
+    /// A wire that no connection leads to.
+    UnknownWire(String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::Parse(err) => err.fmt(f),
+            CircuitError::UnknownWire(wire) => write!(f, "no signal is provided to wire {wire}"),
+        }
+    }
+}
+
+impl From<ParseError> for CircuitError {
+    fn from(err: ParseError) -> Self {
+        CircuitError::Parse(err)
+    }
+}
+
+/// Signals that have been resolved so far. Only valid for the circuit they were resolved on.
+type Signals<'a> = HashMap<&'a str, u16>;
+
+fn resolve_input<'a>(
+    connections: &'a Connections,
+    input: &'a Input,
+    signals: &mut Signals<'a>,
+) -> Option<u16> {
+    match input {
+        Input::Constant(constant) => Some(*constant),
+        Input::Wire(wire) => resolve_connection(connections, wire, signals),
+    }
+}
+
+fn resolve_connection<'a>(
+    connections: &'a Connections,
+    dest: &'a str,
+    signals: &mut Signals<'a>,
+) -> Option<u16> {
+    if let Some(signal) = signals.get(dest) {
+        return Some(*signal);
+    }
+
+    let mut resolve = |input: &'a Input| resolve_input(connections, input, signals);
+
+    let signal = match connections.get(dest)? {
+        Source::And(left, right) => resolve(left)? & resolve(right)?,
+        Source::Or(left, right) => resolve(left)? | resolve(right)?,
+        Source::Not(right) => !resolve(right)?,
+        Source::LShift(left, right) => resolve(left)? << resolve(right)?,
+        Source::RShift(left, right) => resolve(left)? >> resolve(right)?,
+        Source::Signal(signal) => resolve(signal)?,
+    };
+
+    advent_of_code::trace!("{dest} = {signal}");
+    signals.insert(dest, signal);
+    Some(signal)
+}
+
+fn resolve(connections: &Connections, wire: &str) -> Result<u16, CircuitError> {
+    resolve_connection(connections, wire, &mut Signals::new())
+        .ok_or_else(|| CircuitError::UnknownWire(wire.to_string()))
+}
+
+pub fn part_one(input: String, params: &Params) -> Result<u16, CircuitError> {
+    let connections = parse_input(&input)?;
+
+    resolve(&connections, &params.wire)
+}
+
+pub fn part_two(input: String, params: &Params) -> Result<u16, CircuitError> {
+    let mut connections = parse_input(&input)?;
+    let signal = resolve(&connections, &params.wire)?;
+
+    advent_of_code::debug!("overriding wire {} with {signal}", params.override_wire);
+    *connections
+        .get_mut(&params.override_wire)
+        .ok_or_else(|| CircuitError::UnknownWire(params.override_wire.clone()))? =
+        Source::Signal(Input::Constant(signal));
+
+    resolve(&connections, &params.wire)
+}
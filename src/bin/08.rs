@@ -1,77 +1,215 @@
-advent_of_code::solution!(8);
+use std::fmt::Display;
 
-enum UnescapeState {
-    Normal,
-    Backslash,
-    Hex,
+use advent_of_code::template::property::Rng;
+
+advent_of_code::solution!(8, generate = generate_input);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiteralErrorReason {
+    MissingOpeningQuote,
+    MissingClosingQuote,
+    UnescapedQuote,
+    UnknownEscape(char),
+    InvalidHexDigit(char),
+    TruncatedEscape,
 }
 
-fn escaping_length_change(s: &str) -> u32 {
-    let mut count = 0;
-    let mut state = UnescapeState::Normal;
-
-    for c in s.chars() {
-        match state {
-            UnescapeState::Normal => {
-                if c == '\\' {
-                    state = UnescapeState::Backslash;
-                } else {
-                    count += 1;
-                }
+/// A string literal that could not be decoded, along with the byte offset at which decoding failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LiteralError {
+    position: usize,
+    reason: LiteralErrorReason,
+}
+
+impl LiteralError {
+    fn new(position: usize, reason: LiteralErrorReason) -> Self {
+        Self { position, reason }
+    }
+}
+
+impl Display for LiteralError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid string literal at position {}: ", self.position)?;
+
+        match self.reason {
+            LiteralErrorReason::MissingOpeningQuote => write!(f, "expected an opening '\"'"),
+            LiteralErrorReason::MissingClosingQuote => write!(f, "expected a closing '\"'"),
+            LiteralErrorReason::UnescapedQuote => write!(f, "'\"' must be escaped"),
+            LiteralErrorReason::UnknownEscape(c) => write!(f, "unknown escape sequence '\\{c}'"),
+            LiteralErrorReason::InvalidHexDigit(c) => write!(f, "'{c}' is not a hex digit"),
+            LiteralErrorReason::TruncatedEscape => write!(f, "escape sequence is cut short"),
+        }
+    }
+}
+
+fn hex_value(literal: &[u8], position: usize) -> Result<u8, LiteralError> {
+    let digit = *literal.get(position).ok_or(LiteralError::new(
+        position,
+        LiteralErrorReason::TruncatedEscape,
+    ))?;
+
+    (digit as char)
+        .to_digit(16)
+        .map(|value| value as u8)
+        .ok_or(LiteralError::new(
+            position,
+            LiteralErrorReason::InvalidHexDigit(digit as char),
+        ))
+}
+
+/// Decodes a double-quoted string literal into the bytes it represents.
+fn unescape(literal: &str) -> Result<Vec<u8>, LiteralError> {
+    let literal = literal.as_bytes();
+
+    if literal.first() != Some(&b'"') {
+        return Err(LiteralError::new(
+            0,
+            LiteralErrorReason::MissingOpeningQuote,
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(literal.len());
+    let mut position = 1;
+
+    loop {
+        match literal.get(position) {
+            None => {
+                return Err(LiteralError::new(
+                    position,
+                    LiteralErrorReason::MissingClosingQuote,
+                ))
             }
-            UnescapeState::Backslash => {
-                if c == 'x' {
-                    state = UnescapeState::Hex;
-                } else {
-                    state = UnescapeState::Normal;
-                    count += 1;
-                }
+            Some(b'"') if position == literal.len() - 1 => break,
+            Some(b'"') => {
+                return Err(LiteralError::new(
+                    position,
+                    LiteralErrorReason::UnescapedQuote,
+                ))
             }
-            UnescapeState::Hex => {
-                state = UnescapeState::Normal;
+            Some(b'\\') => match literal.get(position + 1) {
+                Some(b'\\') => {
+                    bytes.push(b'\\');
+                    position += 2;
+                }
+                Some(b'"') => {
+                    bytes.push(b'"');
+                    position += 2;
+                }
+                Some(b'x') => {
+                    let high = hex_value(literal, position + 2)?;
+                    let low = hex_value(literal, position + 3)?;
+                    bytes.push(high << 4 | low);
+                    position += 4;
+                }
+                Some(c) => {
+                    return Err(LiteralError::new(
+                        position + 1,
+                        LiteralErrorReason::UnknownEscape(*c as char),
+                    ))
+                }
+                None => {
+                    return Err(LiteralError::new(
+                        position + 1,
+                        LiteralErrorReason::TruncatedEscape,
+                    ))
+                }
+            },
+            Some(c) => {
+                bytes.push(*c);
+                position += 1;
             }
         }
     }
 
-    count -= 2; // Adjust for leading/trailing "'s
+    Ok(bytes)
+}
+
+/// Encodes bytes as a double-quoted string literal that `unescape` decodes back into the same bytes.
+fn escape(bytes: &[u8]) -> String {
+    let mut literal = String::with_capacity(bytes.len() + 2);
+    literal.push('"');
+
+    for &byte in bytes {
+        match byte {
+            b'"' => literal.push_str("\\\""),
+            b'\\' => literal.push_str("\\\\"),
+            b' '..=b'~' => literal.push(byte as char),
+            _ => literal.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
 
-    s.len() as u32 - count
+    literal.push('"');
+    literal
 }
 
-fn unescaping_length_change(s: &str) -> u32 {
-    let mut count = 0;
+/// Encodes text the way the puzzle does, which only escapes quotes and backslashes. Unlike `escape`, any other
+/// character is kept as it is.
+fn encode(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
 
-    for c in s.chars() {
+    for c in text.chars() {
         match c {
-            // Add an additional count for the '\' on character's needing escaping
-            '"' => count += 2,
-            '\\' => count += 2,
-            _ => count += 1,
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            _ => literal.push(c),
         }
     }
 
-    count += 2; // Adjust for surrounding "'s
-
-    count - s.len() as u32
+    literal.push('"');
+    literal
 }
-// let mut count = 0;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    Some(input.lines().map(escaping_length_change).sum())
+pub fn part_one(input: &str) -> Result<u32, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            unescape(line)
+                .map(|bytes| (line.len() - bytes.len()) as u32)
+                .map_err(|err| format!("line {}: {err}", index + 1))
+        })
+        .sum()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    Some(input.lines().map(unescaping_length_change).sum())
+    Some(
+        input
+            .lines()
+            .map(|line| (encode(line).len() - line.len()) as u32)
+            .sum(),
+    )
+}
+
+/// String literals with escaped quotes, backslashes and hexadecimal characters. The size is the number of literals.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let letters: Vec<u8> = (b'a'..=b'z').collect();
+
+    (0..size.unwrap_or(300))
+        .map(|_| {
+            let bytes: Vec<u8> = (0..=rng.below(30))
+                .map(|_| match rng.below(10) {
+                    0 => b'"',
+                    1 => b'\\',
+                    2 => rng.below(256) as u8,
+                    _ => *rng.pick(&letters),
+                })
+                .collect();
+            escape(&bytes)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::property::{self, Gen, Property};
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+        assert_eq!(result, Ok(12));
     }
 
     #[test]
@@ -79,4 +217,93 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(19));
     }
+
+    #[test]
+    fn test_unescape() {
+        assert_eq!(unescape(r#""""#), Ok(vec![]));
+        assert_eq!(unescape(r#""aaa\"aaa""#), Ok(b"aaa\"aaa".to_vec()));
+        assert_eq!(unescape(r#""\\\x27""#), Ok(b"\\'".to_vec()));
+        assert_eq!(unescape(r#""\xff""#), Ok(vec![0xff]));
+    }
+
+    #[test]
+    fn test_unescape_errors() {
+        use LiteralErrorReason::*;
+
+        let error = |position, reason| Err(LiteralError::new(position, reason));
+
+        assert_eq!(unescape(""), error(0, MissingOpeningQuote));
+        assert_eq!(unescape("abc\""), error(0, MissingOpeningQuote));
+        assert_eq!(unescape("\"abc"), error(4, MissingClosingQuote));
+        assert_eq!(unescape("\""), error(1, MissingClosingQuote));
+        assert_eq!(unescape(r#""a"b""#), error(2, UnescapedQuote));
+        assert_eq!(unescape(r#""\n""#), error(2, UnknownEscape('n')));
+        assert_eq!(unescape(r#""\x4g""#), error(4, InvalidHexDigit('g')));
+        assert_eq!(unescape(r#""\x4""#), error(4, InvalidHexDigit('"')));
+        assert_eq!(unescape(r#""\x"#), error(3, TruncatedEscape));
+        assert_eq!(unescape(r#""\"#), error(2, TruncatedEscape));
+
+        assert_eq!(
+            part_one("\"\"\n\"\\q\""),
+            Err(
+                "line 2: invalid string literal at position 2: unknown escape sequence '\\q'"
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(b""), r#""""#);
+        assert_eq!(escape(b"aaa\"aaa"), r#""aaa\"aaa""#);
+        assert_eq!(escape(b"\\'\n"), r#""\\'\x0a""#);
+        assert_eq!(escape(&[0xff]), r#""\xff""#);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(r#""""#), r#""\"\"""#);
+        assert_eq!(encode(r#""\x27""#), r#""\"\\x27\"""#);
+        assert_eq!(encode("\"\t\u{e9}\""), "\"\\\"\t\u{e9}\\\"\"");
+        assert_eq!(part_two("\"\t\""), Some(4));
+    }
+
+    /// Byte strings, biased towards the bytes that need escaping.
+    fn bytes() -> Gen<Vec<u8>> {
+        Gen::new(
+            |rng, size| {
+                let len = property::length(rng, size, 32);
+                (0..len)
+                    .map(|_| match rng.below(4) {
+                        0 => *rng.pick(b"\"\\x"),
+                        _ => rng.next_u64() as u8,
+                    })
+                    .collect()
+            },
+            |bytes: &Vec<u8>| property::shrink_vec(bytes),
+        )
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        Property::new(DAY, "escape_round_trip")
+            .cases(1000)
+            .check(&bytes(), |bytes| {
+                unescape(&escape(bytes)).as_ref() == Ok(bytes)
+            });
+    }
+
+    #[test]
+    fn test_unescape_round_trip() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+
+        for line in input.lines() {
+            let bytes = unescape(line).unwrap();
+            assert_eq!(unescape(&escape(&bytes)), Ok(bytes));
+            assert_eq!(
+                unescape(&escape(line.as_bytes())),
+                Ok(line.as_bytes().to_vec())
+            );
+        }
+    }
 }
@@ -3,19 +3,23 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
+use advent_of_code::parse::{parse_lines, ParseError};
+use advent_of_code::template::{explain, property::Rng};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::{
-        self,
-        complete::{alpha1, newline},
-    },
-    multi::separated_list1,
+    character::{self, complete::alpha1},
     sequence::separated_pair,
     IResult,
 };
 
-advent_of_code::solution!(9);
+advent_of_code::solution!(
+    9,
+    variants(1) { held_karp = part_one_held_karp },
+    variants(2) { held_karp = part_two_held_karp },
+    generate = generate_input,
+    scaling = 2..=9
+);
 
 type Location = String;
 type Distance = ((Location, Location), u32);
@@ -29,16 +33,8 @@ fn parse_distance(input: &str) -> IResult<&str, Distance> {
     Ok((input, ((l1.to_string(), l2.to_string()), distance)))
 }
 
-fn parse_distances(input: &str) -> IResult<&str, DistanceMap> {
-    let (input, distances) = separated_list1(newline, parse_distance)(input)?;
-
-    Ok((input, distances.into_iter().collect()))
-}
-
-fn parse_input(input: &str) -> DistanceMap {
-    let (_, distances) = parse_distances(input).expect("should parse");
-
-    distances
+fn parse_input(input: &str) -> Result<DistanceMap, ParseError> {
+    Ok(parse_lines(input, parse_distance)?.into_iter().collect())
 }
 
 fn get_map_locations(map: &DistanceMap) -> HashSet<Location> {
@@ -52,48 +48,180 @@ fn get_map_locations(map: &DistanceMap) -> HashSet<Location> {
     locations
 }
 
-fn calculate_distance<F>(
-    locations: &HashSet<Location>,
+fn get_distance(map: &DistanceMap, l1: &Location, l2: &Location) -> u32 {
+    if let Some(d) = map.get(&(l1.clone(), l2.clone())) {
+        *d
+    } else if let Some(d) = map.get(&(l2.clone(), l1.clone())) {
+        *d
+    } else {
+        panic!()
+    }
+}
+
+/// Finds the best route visiting every location once, returning its distance and the route itself.
+fn calculate_distance<'a, F>(
+    locations: &'a HashSet<Location>,
     map: &DistanceMap,
     compare: F,
     init: u32,
-) -> u32
+) -> (u32, Vec<&'a Location>)
 where
     F: Fn(u32, u32) -> u32,
 {
     let mut distance = init;
+    let mut route = vec![];
 
     for permutation in locations.iter().permutations(locations.len()) {
-        let mut d = 0;
-
-        for (l1, l2) in permutation.into_iter().tuple_windows() {
-            d += if let Some(d) = map.get(&(l1.clone(), l2.clone())) {
-                *d
-            } else if let Some(d) = map.get(&(l2.clone(), l1.clone())) {
-                *d
-            } else {
-                panic!()
-            };
+        let d = permutation
+            .iter()
+            .tuple_windows()
+            .map(|(l1, l2)| get_distance(map, l1, l2))
+            .sum();
+
+        if compare(distance, d) != distance {
+            distance = d;
+            route = permutation;
         }
+    }
+
+    (distance, route)
+}
+
+/// Finds the distance of the best route with the Held-Karp algorithm, in O(2ⁿ·n²) instead of O(n!).
+fn calculate_distance_held_karp<F>(
+    locations: &HashSet<Location>,
+    map: &DistanceMap,
+    compare: F,
+    init: u32,
+) -> u32
+where
+    F: Fn(u32, u32) -> u32,
+{
+    let locations: Vec<&Location> = locations.iter().collect();
+    let n = locations.len();
+    if n == 0 {
+        // like the brute force, which sums the legs of the single empty route.
+        return 0;
+    }
+
+    let distances: Vec<Vec<u32>> = locations
+        .iter()
+        .map(|l1| {
+            locations
+                .iter()
+                .map(|l2| {
+                    if l1 == l2 {
+                        0
+                    } else {
+                        get_distance(map, l1, l2)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    // best[visited][last]: the best distance of a route visiting the locations in `visited`, ending at `last`.
+    let mut best: Vec<Vec<Option<u32>>> = vec![vec![None; n]; 1 << n];
+    for start in 0..n {
+        best[1 << start][start] = Some(0);
+    }
+
+    for visited in 1..1usize << n {
+        for last in 0..n {
+            let Some(distance) = best[visited][last] else {
+                continue;
+            };
 
-        distance = compare(distance, d);
+            for next in (0..n).filter(|next| visited & (1 << next) == 0) {
+                let d = distance + distances[last][next];
+                let entry = &mut best[visited | (1 << next)][next];
+                *entry = Some(entry.map_or(d, |e| compare(e, d)));
+            }
+        }
     }
 
-    distance
+    best[(1 << n) - 1]
+        .iter()
+        .flatten()
+        .copied()
+        .fold(init, compare)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = parse_input(input);
+fn explain_route(map: &DistanceMap, route: &[&Location]) {
+    explain::detail("route", || route.iter().join(" -> "));
+    explain::detail("legs", || {
+        route
+            .iter()
+            .tuple_windows()
+            .map(|(l1, l2)| format!("{l1} -> {l2} = {}", get_distance(map, l1, l2)))
+            .join("\n")
+    });
+}
+
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let map = parse_input(input)?;
     let locations = get_map_locations(&map);
 
-    Some(calculate_distance(&locations, &map, min, u32::MAX))
+    let (distance, route) = calculate_distance(&locations, &map, min, u32::MAX);
+    explain_route(&map, &route);
+
+    Ok(distance)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = parse_input(input);
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let map = parse_input(input)?;
     let locations = get_map_locations(&map);
 
-    Some(calculate_distance(&locations, &map, max, u32::MIN))
+    let (distance, route) = calculate_distance(&locations, &map, max, u32::MIN);
+    explain_route(&map, &route);
+
+    Ok(distance)
+}
+
+pub fn part_one_held_karp(input: &str) -> Result<u32, ParseError> {
+    let map = parse_input(input)?;
+    Ok(calculate_distance_held_karp(
+        &get_map_locations(&map),
+        &map,
+        min,
+        u32::MAX,
+    ))
+}
+
+pub fn part_two_held_karp(input: &str) -> Result<u32, ParseError> {
+    let map = parse_input(input)?;
+    Ok(calculate_distance_held_karp(
+        &get_map_locations(&map),
+        &map,
+        max,
+        u32::MIN,
+    ))
+}
+
+const LOCATIONS: [&str; 12] = [
+    "Faerun",
+    "Norrath",
+    "Tristram",
+    "AlphaCentauri",
+    "Arbre",
+    "Snowdin",
+    "Tambi",
+    "Straylight",
+    "Hoth",
+    "Tatooine",
+    "Dagobah",
+    "Endor",
+];
+
+/// Distances between all pairs of locations. The size is the number of locations, at most 12.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let count = size.unwrap_or(8).min(LOCATIONS.len());
+
+    LOCATIONS[..count]
+        .iter()
+        .tuple_combinations()
+        .map(|(l1, l2)| format!("{l1} to {l2} = {}", rng.below(140) + 10))
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -103,12 +231,38 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(605));
+        assert_eq!(result, Ok(605));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(982));
+        assert_eq!(result, Ok(982));
+    }
+
+    #[test]
+    fn test_held_karp() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(part_one_held_karp(&input), Ok(605));
+        assert_eq!(part_two_held_karp(&input), Ok(982));
+    }
+
+    #[test]
+    fn test_held_karp_empty_map() {
+        assert_eq!(part_one_held_karp(""), part_one(""));
+        assert_eq!(part_two_held_karp(""), part_two(""));
+    }
+
+    #[test]
+    fn test_explain_route() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let (_, details) = explain::capture(|| part_one(&input));
+        let route = &details[0];
+
+        assert_eq!(route.key, "route");
+        assert!(
+            ["London -> Dublin -> Belfast", "Belfast -> Dublin -> London"]
+                .contains(&route.value.as_str())
+        );
     }
 }
@@ -1,7 +1,21 @@
+use advent_of_code::template::{property::Rng, Input};
+use memoize::memoize;
 use rle_vec::RleVec;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(
+    10,
+    params {
+        rounds_one: u32 = 40,
+        rounds_two: u32 = 50,
+    },
+    reset = clear_caches,
+    generate = generate_input,
+    scaling = 1..=8
+);
 
+// Part two repeats the rounds of part one, which are looked up when both run in the same thread. The runner clears
+// the cache before every run with `reset`, so each part is still timed in full.
+#[memoize]
 fn look_and_say(input: String) -> String {
     let mut output = "".to_string();
     let rle: RleVec<char> = input.chars().collect();
@@ -14,39 +28,74 @@ fn look_and_say(input: String) -> String {
     output
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut s = input.to_string();
+/// Called by the runner before every run, so that benchmarks don't just measure cache lookups.
+pub fn clear_caches() {
+    memoized_flush_look_and_say();
+}
+
+fn look_and_say_length(input: &Input, rounds: u32) -> u32 {
+    let mut s = input.single_line().to_string();
 
-    for _ in 0..40 {
+    for _ in 0..rounds {
         s = look_and_say(s);
     }
 
-    Some(s.len() as u32)
+    s.len() as u32
+}
+
+pub fn part_one(input: &Input, params: &Params) -> Option<u32> {
+    Some(look_and_say_length(input, params.rounds_one))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut s = input.to_string();
+pub fn part_two(input: &Input, params: &Params) -> Option<u32> {
+    Some(look_and_say_length(input, params.rounds_two))
+}
 
-    for _ in 0..50 {
-        s = look_and_say(s);
+/// A sequence of the digits 1 to 3 without runs of more than three equal digits, which look-and-say never produces.
+/// The size is the number of digits.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let mut digits = String::new();
+
+    while digits.len() < size.unwrap_or(10) {
+        let digit = *rng.pick(&['1', '2', '3']);
+        if !digits.ends_with(&digit.to_string().repeat(3)) {
+            digits.push(digit);
+        }
     }
 
-    Some(s.len() as u32)
+    digits
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
     #[test]
     fn test_part_one() {
-        // let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        // assert_eq!(result, None);
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY).into(),
+            &Params::example(),
+        );
+        assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        // let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        // assert_eq!(result, None);
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY).into(),
+            &Params::example(),
+        );
+        assert_eq!(result, Some(8));
+    }
+
+    #[test]
+    fn test_clear_caches() {
+        let cached = || MEMOIZED_MAPPING_LOOK_AND_SAY.with(|cache| cache.borrow().len());
+
+        part_one(&"1".into(), &Params::example());
+        assert_eq!(cached(), 5);
+
+        clear_caches();
+        assert_eq!(cached(), 0);
     }
 }
@@ -1,6 +1,9 @@
+use std::collections::HashMap;
+
+use advent_of_code::template::{explain, property::Rng, Input};
 use itertools::Itertools;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, generate = generate_input);
 
 fn increment_character(c: char) -> char {
     match c {
@@ -92,6 +95,56 @@ fn is_valid(password: &str) -> bool {
     contains_straight(password) && contains_two_pairs(password) && no_disallowed_letters(password)
 }
 
+type PasswordRule = (&'static str, fn(&str) -> bool);
+
+const RULES: [PasswordRule; 3] = [
+    ("no straight of three letters", contains_straight),
+    ("fewer than two different pairs", contains_two_pairs),
+    ("contains i, o or l", no_disallowed_letters),
+];
+
+/// Describes the first rule that rejects the password, if any.
+fn failed_rule(password: &str) -> Option<&'static str> {
+    RULES
+        .iter()
+        .find(|(_, rule)| !rule(password))
+        .map(|(description, _)| *description)
+}
+
+/// Explains which rules rejected the candidates between `password` and the next valid password.
+fn explain_rejections(password: &str) {
+    if !explain::is_enabled() {
+        return;
+    }
+
+    let mut rejections: HashMap<&str, usize> = HashMap::new();
+    let mut last_rejected = None;
+    let mut password = password.to_string();
+
+    loop {
+        password = increment_password(password);
+
+        match failed_rule(&password) {
+            Some(rule) => {
+                *rejections.entry(rule).or_default() += 1;
+                last_rejected = Some((password.clone(), rule));
+            }
+            None => break,
+        }
+    }
+
+    explain::detail("rejected", || {
+        RULES
+            .iter()
+            .map(|(rule, _)| format!("{}: {rule}", rejections.get(rule).unwrap_or(&0)))
+            .join("\n")
+    });
+
+    if let Some((password, rule)) = last_rejected {
+        explain::detail("last rejected", || format!("{password} ({rule})"));
+    }
+}
+
 fn next_password(mut password: String) -> String {
     loop {
         password = increment_password(password);
@@ -102,12 +155,24 @@ fn next_password(mut password: String) -> String {
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    Some(next_password(input.to_string()))
+pub fn part_one(input: &Input) -> Option<String> {
+    let password = input.single_line();
+    explain_rejections(password);
+
+    Some(next_password(password.to_string()))
+}
+
+pub fn part_two(input: &Input) -> Option<String> {
+    let password = next_password(input.single_line().to_string());
+    explain_rejections(&password);
+
+    Some(next_password(password))
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    Some(next_password(next_password(input.to_string())))
+/// A password of 8 lowercase letters that doesn't contain any of the forbidden ones. The size is ignored.
+fn generate_input(rng: &mut Rng, _size: Option<usize>) -> String {
+    let letters: Vec<char> = ('a'..='z').filter(|c| !"iol".contains(*c)).collect();
+    (0..8).map(|_| *rng.pick(&letters)).collect()
 }
 
 #[cfg(test)]
@@ -116,13 +181,27 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY).into());
         assert_eq!(result, Some("ghjaabcc".to_string()));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY).into());
         assert_eq!(result, Some("ghjbbcdd".to_string()));
     }
+
+    #[test]
+    fn test_failed_rule() {
+        assert_eq!(
+            failed_rule("abcdefgh"),
+            Some("fewer than two different pairs")
+        );
+        assert_eq!(
+            failed_rule("abbceffg"),
+            Some("no straight of three letters")
+        );
+        assert_eq!(failed_rule("abcdffii"), Some("contains i, o or l"));
+        assert_eq!(failed_rule("abcdffaa"), None);
+    }
 }
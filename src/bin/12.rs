@@ -1,6 +1,7 @@
+use advent_of_code::template::property::Rng;
 use json::JsonValue;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, generate = generate_input);
 
 fn sum_numbers(value: &JsonValue) -> i32 {
     match value {
@@ -42,6 +43,48 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(sum_numbers_no_red(&json))
 }
 
+/// A JSON document of nested arrays and objects, some of them red. The size is the number of numbers and strings.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let mut remaining = size.unwrap_or(5000);
+    let mut values = vec![];
+
+    while remaining > 0 {
+        values.push(generate_value(rng, 0, &mut remaining));
+    }
+
+    format!("[{}]", values.join(","))
+}
+
+fn generate_value(rng: &mut Rng, depth: usize, remaining: &mut usize) -> String {
+    if depth < 5 && rng.below(4) == 0 {
+        let values: Vec<String> = (0..=rng.below(6))
+            .map(|_| generate_value(rng, depth + 1, remaining))
+            .collect();
+
+        return if rng.below(2) == 0 {
+            format!("[{}]", values.join(","))
+        } else {
+            let fields: Vec<String> = values
+                .iter()
+                .zip('a'..)
+                .map(|(value, key)| format!("\"{key}\":{value}"))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        };
+    }
+
+    *remaining = remaining.saturating_sub(1);
+
+    if rng.below(2) == 0 {
+        (rng.below(400) as i32 - 200).to_string()
+    } else {
+        format!(
+            "\"{}\"",
+            rng.pick(&["red", "green", "blue", "orange", "violet"])
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,17 +1,23 @@
-use std::{
-    cmp::max,
-    collections::{HashMap, HashSet},
-};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
+use advent_of_code::parse::{parse_lines, ParseError};
+use advent_of_code::template::{explain, property::Rng};
 use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, digit1, newline},
-    multi::separated_list1,
+    character::complete::{alpha1, digit1},
     IResult,
 };
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(
+    13,
+    params {
+        guest: String = "Me",
+    },
+    generate = generate_input,
+    scaling = 2..=8
+);
 
 type Person = String;
 type Relationship = ((Person, Person), i32);
@@ -33,14 +39,40 @@ fn parse_relationship(input: &str) -> IResult<&str, Relationship> {
     Ok((input, ((p1.to_string(), p2.to_string()), sign * happiness)))
 }
 
-fn parse_relationships(input: &str) -> IResult<&str, Vec<Relationship>> {
-    separated_list1(newline, parse_relationship)(input)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatingError {
+    Parse(ParseError),
+    /// The input has no relationships, so there is nobody to seat.
+    NoPeople,
+    /// The `guest` param names someone who is already in the input.
+    GuestExists(Person),
 }
 
-fn parse_input(input: &str) -> Relationships {
-    let (_, relationships_vec) = parse_relationships(input).expect("should parse");
+impl Display for SeatingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeatingError::Parse(err) => err.fmt(f),
+            SeatingError::NoPeople => write!(f, "no relationships to seat anyone by"),
+            SeatingError::GuestExists(guest) => {
+                write!(
+                    f,
+                    "guest {guest:?} is already among the people in the input"
+                )
+            }
+        }
+    }
+}
+
+impl From<ParseError> for SeatingError {
+    fn from(err: ParseError) -> Self {
+        SeatingError::Parse(err)
+    }
+}
 
-    relationships_vec.into_iter().collect()
+fn parse_input(input: &str) -> Result<Relationships, ParseError> {
+    Ok(parse_lines(input, parse_relationship)?
+        .into_iter()
+        .collect())
 }
 
 fn get_people(relationships: &Relationships) -> HashSet<Person> {
@@ -54,56 +86,111 @@ fn get_people(relationships: &Relationships) -> HashSet<Person> {
     people
 }
 
-fn find_max_happiness(relationships: &Relationships) -> i32 {
+fn get_happiness(relationships: &Relationships, p1: &str, p2: &str) -> i32 {
+    relationships
+        .get(&(p1.to_string(), p2.to_string()))
+        .expect("should exist")
+        + relationships
+            .get(&(p2.to_string(), p1.to_string()))
+            .expect("should exist")
+}
+
+/// Finds the seating around the table with the highest total change in happiness,
+/// returning that change and the seating order.
+fn find_max_happiness(relationships: &Relationships) -> Result<(i32, Vec<Person>), SeatingError> {
     let people: Vec<Person> = get_people(relationships).into_iter().collect();
 
+    if people.is_empty() {
+        return Err(SeatingError::NoPeople);
+    }
+
     let mut max_happiness = 0;
+    let mut seating = vec![];
 
     for permutation in people.iter().permutations(people.len()) {
         let mut happiness = 0;
 
         for (p1, p2) in permutation.iter().tuple_windows() {
-            happiness += relationships
-                .get(&(p1.to_string(), p2.to_string()))
-                .expect("should exist");
-            happiness += relationships
-                .get(&(p2.to_string(), p1.to_string()))
-                .expect("should exist");
+            happiness += get_happiness(relationships, p1, p2);
         }
 
         // Handle first/last pair the tuple_windows won't cover
         let first = permutation.first().expect("should exist");
         let last = permutation.last().expect("should exist");
-        happiness += relationships
-            .get(&(first.to_string(), last.to_string()))
-            .expect("should exist");
-        happiness += relationships
-            .get(&(last.to_string(), first.to_string()))
-            .expect("should exist");
-
-        max_happiness = max(max_happiness, happiness);
+        happiness += get_happiness(relationships, first, last);
+
+        if seating.is_empty() || happiness > max_happiness {
+            max_happiness = happiness;
+            seating = permutation;
+        }
     }
 
-    max_happiness
+    Ok((max_happiness, seating.into_iter().cloned().collect()))
 }
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let relationships = parse_input(input);
+fn explain_seating(relationships: &Relationships, seating: &[Person]) {
+    explain::detail("seating", || seating.join(", "));
+    explain::detail("neighbours", || {
+        seating
+            .iter()
+            .circular_tuple_windows()
+            .map(|(p1, p2)| format!("{p1} & {p2}: {:+}", get_happiness(relationships, p1, p2)))
+            .join("\n")
+    });
+}
 
-    Some(find_max_happiness(&relationships))
+pub fn part_one(input: &str, _params: &Params) -> Result<i32, SeatingError> {
+    let relationships = parse_input(input)?;
+
+    let (happiness, seating) = find_max_happiness(&relationships)?;
+    explain_seating(&relationships, &seating);
+
+    Ok(happiness)
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let mut relationships = parse_input(input);
+pub fn part_two(input: &str, params: &Params) -> Result<i32, SeatingError> {
+    let mut relationships = parse_input(input)?;
     let people = get_people(&relationships);
-    let me = "Me".to_string();
+
+    if people.contains(&params.guest) {
+        return Err(SeatingError::GuestExists(params.guest.clone()));
+    }
 
     for person in people {
-        relationships.insert((me.clone(), person.to_string()), 0);
-        relationships.insert((person.to_string(), me.clone()), 0);
+        relationships.insert((params.guest.clone(), person.to_string()), 0);
+        relationships.insert((person.to_string(), params.guest.clone()), 0);
     }
 
-    Some(find_max_happiness(&relationships))
+    let (happiness, seating) = find_max_happiness(&relationships)?;
+    explain_seating(&relationships, &seating);
+
+    Ok(happiness)
+}
+
+const GUESTS: [&str; 10] = [
+    "Alice", "Bob", "Carol", "David", "Eric", "Frank", "George", "Mallory", "Nora", "Oscar",
+];
+
+/// Happiness changes between all pairs of guests. The size is the number of guests, at most 10.
+fn generate_input(rng: &mut Rng, size: Option<usize>) -> String {
+    let guests = &GUESTS[..size.unwrap_or(8).min(GUESTS.len())];
+
+    guests
+        .iter()
+        .flat_map(|g1| {
+            guests
+                .iter()
+                .filter(move |g2| *g2 != g1)
+                .map(move |g2| (g1, g2))
+        })
+        .map(|(g1, g2)| {
+            let change = if rng.below(2) == 0 { "gain" } else { "lose" };
+            format!(
+                "{g1} would {change} {} happiness units by sitting next to {g2}.",
+                rng.below(100)
+            )
+        })
+        .join("\n")
 }
 
 #[cfg(test)]
@@ -112,13 +199,47 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(330));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(330));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(286));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::example(),
+        );
+        assert_eq!(result, Ok(286));
+    }
+
+    #[test]
+    fn test_guest_exists() {
+        let params = Params {
+            guest: "Alice".into(),
+        };
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &params,
+        );
+        assert_eq!(result, Err(SeatingError::GuestExists("Alice".into())));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "guest \"Alice\" is already among the people in the input"
+        );
+    }
+
+    #[test]
+    fn test_no_people() {
+        assert_eq!(
+            part_one("", &Params::default()),
+            Err(SeatingError::NoPeople)
+        );
+        assert_eq!(
+            part_two("", &Params::default()),
+            Err(SeatingError::NoPeople)
+        );
     }
 }
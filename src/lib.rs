@@ -1,3 +1,5 @@
+pub mod parse;
+pub mod rules;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
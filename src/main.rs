@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, archive, download, generate_input, read, reset, scaffold, solve, test_all, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,8 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::{scaffold, solve};
+    use advent_of_code::template::{Day, InputSource};
     use std::process;
 
     pub enum AppArguments {
@@ -21,12 +24,30 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            refresh_docs: bool,
+            dir: bool,
+            template: String,
         },
         Solve {
             day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            options: solve::Options,
+        },
+        Archive {
+            day: Day,
+        },
+        GenerateInput {
+            day: Day,
+            seed: Option<u64>,
+            size: Option<usize>,
+            overwrite: bool,
+        },
+        Reset {
+            day: Day,
+            dir: bool,
+            template: Option<String>,
+        },
+        TestAll {
+            slow: bool,
         },
         All {
             release: bool,
@@ -35,6 +56,8 @@ mod args {
             all: bool,
             day: Option<Day>,
             store: bool,
+            scaling: bool,
+            instructions: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -50,11 +73,27 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let scaling = args.contains("--scaling");
+                let instructions = args.contains("--instructions");
+                let day = args.opt_free_from_str()?;
+
+                if scaling && (day.is_none() || all || store) {
+                    return Err(
+                        "--scaling requires a day and can not be combined with --all or --store"
+                            .into(),
+                    );
+                }
+
+                if scaling && instructions {
+                    return Err("--scaling can not be combined with --instructions".into());
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    scaling,
+                    instructions,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -63,17 +102,63 @@ mod args {
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
+            Some("scaffold") => {
+                let dir = args.contains("--dir");
+                let template: Option<String> = args.opt_value_from_str("--template")?;
+
+                if dir && template.is_some() {
+                    return Err("--template can not be combined with --dir".into());
+                }
+
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download: args.contains("--download"),
+                    overwrite: args.contains("--overwrite"),
+                    refresh_docs: args.contains("--refresh-docs"),
+                    dir,
+                    template: template.unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.to_string()),
+                }
+            }
+            Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                options: solve::Options {
+                    release: args.contains("--release"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    explain: args.contains("--explain"),
+                    variants: args.contains("--variants"),
+                    verbosity: parse_verbosity(&mut args),
+                    log_file: args.contains("--log"),
+                    params: args.values_from_str("--param")?,
+                    input_source: parse_input_source(&mut args)?,
+                },
             },
-            Some("solve") => AppArguments::Solve {
+            Some("archive") => AppArguments::Archive {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
+            Some("generate-input") => AppArguments::GenerateInput {
+                day: args.free_from_str()?,
+                seed: args.opt_value_from_str("--seed")?,
+                size: args.opt_value_from_str("--size")?,
+                overwrite: args.contains("--overwrite"),
+            },
+            Some("test-all") => AppArguments::TestAll {
+                slow: args.contains("--slow"),
+            },
+            Some("reset") => {
+                let dir = args.contains("--dir");
+                let template: Option<String> = args.opt_value_from_str("--template")?;
+
+                if dir && template.is_some() {
+                    return Err("--template can not be combined with --dir".into());
+                }
+
+                AppArguments::Reset {
+                    day: args.free_from_str()?,
+                    dir,
+                    template,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -93,6 +178,33 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `-v` or `-vv`.
+    fn parse_verbosity(args: &mut pico_args::Arguments) -> u8 {
+        if args.contains("-vv") {
+            2
+        } else if args.contains("-v") {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Parses `--input <path>`, `--stdin` or `--example [N]`. Needs to run after all other flags were consumed,
+    /// as the optional example number is read from the remaining free arguments.
+    fn parse_input_source(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputSource, pico_args::Error> {
+        if let Some(path) = args.opt_value_from_str("--input")? {
+            Ok(InputSource::File(path))
+        } else if args.contains("--stdin") {
+            Ok(InputSource::Stdin)
+        } else if args.contains("--example") {
+            Ok(InputSource::Example(args.opt_free_from_str()?))
+        } else {
+            Ok(InputSource::Puzzle)
+        }
+    }
 }
 
 fn main() {
@@ -103,30 +215,60 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day: Some(day),
+                scaling: true,
+                ..
+            } => time::handle_scaling(day),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                instructions: true,
+                ..
+            } => time::handle_instructions(day, all, store),
+            AppArguments::Time {
+                day, all, store, ..
+            } => time::handle(day, all, store),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                refresh_docs,
+                dir,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                if dir && !refresh_docs {
+                    scaffold::handle_dir(day, overwrite);
+                } else if !refresh_docs {
+                    scaffold::handle(day, overwrite, &template);
+                }
                 if download {
                     download::handle(day);
                 }
+                if refresh_docs {
+                    scaffold::handle_refresh_docs(day);
+                }
             }
-            AppArguments::Solve {
+            AppArguments::Solve { day, options } => solve::handle(day, &options),
+            AppArguments::Archive { day } => archive::handle(day),
+            AppArguments::GenerateInput {
                 day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                seed,
+                size,
+                overwrite,
+            } => generate_input::handle(day, seed, size, overwrite),
+            AppArguments::TestAll { slow } => test_all::handle(slow),
+            AppArguments::Reset { day, dir, template } => {
+                reset::handle(day, template.as_deref(), dir);
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
+                        scaffold::handle(day, false, scaffold::DEFAULT_TEMPLATE);
                         download::handle(day);
                         read::handle(day)
                     }
@@ -0,0 +1,155 @@
+/// Helpers for running line-oriented `nom` parsers with readable error reporting.
+use std::error::Error;
+use std::fmt::Display;
+
+use nom::error::ErrorKind;
+use nom::IResult;
+
+/// A line of input that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column (in characters) at which parsing stopped.
+    pub column: usize,
+    /// The full text of the offending line.
+    pub text: String,
+    /// What the parser expected to find at `column`.
+    pub expected: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, text: &str, rest: &str, expected: String) -> Self {
+        let offset = text.len() - rest.len();
+
+        Self {
+            line,
+            column: text[..offset].chars().count() + 1,
+            text: text.to_string(),
+            expected,
+        }
+    }
+
+    /// The remainder of the line starting at the error position.
+    pub fn found(&self) -> &str {
+        let offset = self
+            .text
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(self.text.len(), |(offset, _)| offset);
+
+        &self.text[offset..]
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let found = match self.found() {
+            "" => "end of line".to_string(),
+            s => format!("{s:?}"),
+        };
+
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {found}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+fn describe_error_kind(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag => "a fixed piece of text".into(),
+        ErrorKind::Char => "a specific character".into(),
+        ErrorKind::Digit => "a number".into(),
+        ErrorKind::Alpha => "a word".into(),
+        ErrorKind::AlphaNumeric => "a word or number".into(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".into(),
+        ErrorKind::Alt => "one of several alternatives".into(),
+        ErrorKind::Eof => "end of input".into(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+/// Runs `parser` over every line of `input`, requiring each line to be consumed completely.
+/// Trailing newlines at the end of the input are ignored.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .trim_end_matches(['\r', '\n'])
+        .lines()
+        .enumerate()
+        .map(|(index, line)| match parser(line) {
+            Ok(("", value)) => Ok(value),
+            Ok((rest, _)) => Err(ParseError::new(index + 1, line, rest, "end of line".into())),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(ParseError::new(
+                index + 1,
+                line,
+                e.input,
+                describe_error_kind(e.code),
+            )),
+            Err(nom::Err::Incomplete(_)) => {
+                Err(ParseError::new(index + 1, line, "", "more input".into()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::{bytes::complete::tag, character::complete::u32, sequence::separated_pair};
+
+    fn parse_pair(input: &str) -> IResult<&str, (u32, u32)> {
+        separated_pair(u32, tag("x"), u32)(input)
+    }
+
+    #[test]
+    fn parses_all_lines() {
+        assert_eq!(
+            parse_lines("1x2\n3x4\n", parse_pair),
+            Ok(vec![(1, 2), (3, 4)])
+        );
+        assert_eq!(
+            parse_lines("1x2\r\n3x4\r\n", parse_pair),
+            Ok(vec![(1, 2), (3, 4)])
+        );
+        assert_eq!(parse_lines("", parse_pair), Ok(vec![]));
+    }
+
+    #[test]
+    fn reports_position_of_error() {
+        let err = parse_lines("1x2\n3y4\n5x6", parse_pair).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 2);
+        assert_eq!(err.text, "3y4");
+        assert_eq!(err.found(), "y4");
+        assert_eq!(err.expected, "a fixed piece of text");
+        assert_eq!(
+            err.to_string(),
+            r#"line 2, column 2: expected a fixed piece of text, found "y4""#
+        );
+    }
+
+    #[test]
+    fn reports_unconsumed_input() {
+        let err = parse_lines("1x2 \n", parse_pair).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.expected, "end of line");
+    }
+
+    #[test]
+    fn reports_blank_lines() {
+        let err = parse_lines("1x2\n\n3x4", parse_pair).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found(), "");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected a number, found end of line"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+/// Composable predicates over strings, e.g. for classifying "nice" strings.
+///
+/// Every leaf rule looks at the string in a single pass. Rules are combined with [`Rule::and`],
+/// [`Rule::or`] and [`Rule::not`], and [`Rule::check`] reports which rule rejected a string.
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// The rule that rejected a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleFailure {
+    pub rule: String,
+}
+
+impl Display for RuleFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed rule: {}", self.rule)
+    }
+}
+
+pub trait Rule {
+    /// A short description of the rule, used when reporting failures.
+    fn describe(&self) -> String;
+
+    fn is_satisfied(&self, s: &str) -> bool;
+
+    /// Checks the string, returning the most specific rule that rejected it.
+    fn check(&self, s: &str) -> Result<(), RuleFailure> {
+        if self.is_satisfied(s) {
+            Ok(())
+        } else {
+            Err(RuleFailure {
+                rule: self.describe(),
+            })
+        }
+    }
+
+    fn and<R: Rule>(self, other: R) -> And<Self, R>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    fn or<R: Rule>(self, other: R) -> Or<Self, R>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct And<A, B>(A, B);
+
+impl<A: Rule, B: Rule> Rule for And<A, B> {
+    fn describe(&self) -> String {
+        format!("({} and {})", self.0.describe(), self.1.describe())
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        self.0.is_satisfied(s) && self.1.is_satisfied(s)
+    }
+
+    fn check(&self, s: &str) -> Result<(), RuleFailure> {
+        self.0.check(s)?;
+        self.1.check(s)
+    }
+}
+
+pub struct Or<A, B>(A, B);
+
+impl<A: Rule, B: Rule> Rule for Or<A, B> {
+    fn describe(&self) -> String {
+        format!("({} or {})", self.0.describe(), self.1.describe())
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        self.0.is_satisfied(s) || self.1.is_satisfied(s)
+    }
+}
+
+pub struct Not<A>(A);
+
+impl<A: Rule> Rule for Not<A> {
+    fn describe(&self) -> String {
+        format!("not {}", self.0.describe())
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        !self.0.is_satisfied(s)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Requires at least `count` vowels (`aeiou`).
+pub fn min_vowels(count: usize) -> MinVowels {
+    MinVowels(count)
+}
+
+pub struct MinVowels(usize);
+
+impl Rule for MinVowels {
+    fn describe(&self) -> String {
+        format!("at least {} vowels", self.0)
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        self.0 == 0
+            || s.chars()
+                .filter(|c| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u'))
+                .nth(self.0 - 1)
+                .is_some()
+    }
+}
+
+/// Requires a letter that repeats with exactly `gap` letters between, e.g. `aa` for a gap of 0 or
+/// `xyx` for a gap of 1.
+pub fn letter_repeat(gap: usize) -> LetterRepeat {
+    LetterRepeat(gap)
+}
+
+pub struct LetterRepeat(usize);
+
+impl Rule for LetterRepeat {
+    fn describe(&self) -> String {
+        format!("a letter repeating after a gap of {}", self.0)
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        // ring buffer of the last `gap + 1` letters.
+        let mut previous: Vec<char> = Vec::with_capacity(self.0 + 1);
+
+        for (index, c) in s.chars().enumerate() {
+            let slot = index % (self.0 + 1);
+
+            if index > self.0 && previous[slot] == c {
+                return true;
+            }
+
+            if index > self.0 {
+                previous[slot] = c;
+            } else {
+                previous.push(c);
+            }
+        }
+
+        false
+    }
+}
+
+/// Requires a pair of letters that appears at least twice without overlapping, e.g. `xyxy`.
+pub fn repeated_pair() -> RepeatedPair {
+    RepeatedPair
+}
+
+pub struct RepeatedPair;
+
+impl Rule for RepeatedPair {
+    fn describe(&self) -> String {
+        "a non-overlapping repeated pair".into()
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        // index at which each pair was first seen.
+        let mut first_seen: HashMap<(char, char), usize> = HashMap::new();
+        let mut chars = s.chars();
+
+        let Some(mut previous) = chars.next() else {
+            return false;
+        };
+
+        for (index, c) in chars.enumerate() {
+            let first = *first_seen.entry((previous, c)).or_insert(index);
+            if index >= first + 2 {
+                return true;
+            }
+            previous = c;
+        }
+
+        false
+    }
+}
+
+/// Rejects strings containing any of the given substrings.
+pub fn forbidden<I, S>(substrings: I) -> Forbidden
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let substrings: Vec<String> = substrings.into_iter().map(Into::into).collect();
+
+    let mut lengths: Vec<usize> = substrings.iter().map(String::len).collect();
+    lengths.sort_unstable();
+    lengths.dedup();
+
+    let lookup = substrings
+        .iter()
+        .enumerate()
+        .map(|(index, f)| (f.as_bytes().to_vec(), index))
+        .collect();
+
+    Forbidden {
+        substrings,
+        lengths,
+        lookup,
+    }
+}
+
+pub struct Forbidden {
+    substrings: Vec<String>,
+    /// The distinct lengths of the substrings. Each window of one of these lengths is looked up once, e.g. every
+    /// two-byte window when all substrings are pairs.
+    lengths: Vec<usize>,
+    lookup: HashMap<Vec<u8>, usize>,
+}
+
+impl Forbidden {
+    /// The first forbidden substring in `s` and its byte offset.
+    fn find(&self, s: &str) -> Option<(usize, &str)> {
+        let bytes = s.as_bytes();
+
+        (0..bytes.len()).find_map(|index| {
+            self.lengths.iter().find_map(|&len| {
+                let window = bytes.get(index..index + len)?;
+                let f = self.lookup.get(window)?;
+                Some((index, self.substrings[*f].as_str()))
+            })
+        })
+    }
+}
+
+impl Rule for Forbidden {
+    fn describe(&self) -> String {
+        format!("none of {:?}", self.substrings)
+    }
+
+    fn is_satisfied(&self, s: &str) -> bool {
+        self.find(s).is_none()
+    }
+
+    fn check(&self, s: &str) -> Result<(), RuleFailure> {
+        match self.find(s) {
+            Some((index, f)) => Err(RuleFailure {
+                rule: format!("{} (found {f:?} at {index})", self.describe()),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_vowels() {
+        assert!(min_vowels(3).is_satisfied("aei"));
+        assert!(min_vowels(3).is_satisfied("xazegov"));
+        assert!(!min_vowels(3).is_satisfied("dvszwmarrgswjxmb"));
+        assert!(min_vowels(0).is_satisfied(""));
+    }
+
+    #[test]
+    fn finds_letter_repeats() {
+        assert!(letter_repeat(0).is_satisfied("abcdde"));
+        assert!(!letter_repeat(0).is_satisfied("abcde"));
+        assert!(letter_repeat(1).is_satisfied("xyx"));
+        assert!(letter_repeat(1).is_satisfied("aaa"));
+        assert!(!letter_repeat(1).is_satisfied("uurcxstgmygtbstg"));
+        assert!(!letter_repeat(1).is_satisfied("xy"));
+    }
+
+    #[test]
+    fn finds_repeated_pairs() {
+        assert!(repeated_pair().is_satisfied("xyxy"));
+        assert!(repeated_pair().is_satisfied("aabcdefgaa"));
+        assert!(repeated_pair().is_satisfied("aaaa"));
+        assert!(!repeated_pair().is_satisfied("aaa"));
+        assert!(!repeated_pair().is_satisfied("ieodomkazucvgmuy"));
+        assert!(!repeated_pair().is_satisfied(""));
+    }
+
+    #[test]
+    fn rejects_forbidden_substrings() {
+        let rule = forbidden(["ab", "cd"]);
+        assert!(rule.is_satisfied("acbd"));
+        assert!(!rule.is_satisfied("xxcd"));
+        assert_eq!(
+            rule.check("xxcd"),
+            Err(RuleFailure {
+                rule: r#"none of ["ab", "cd"] (found "cd" at 2)"#.into()
+            })
+        );
+
+        let rule = forbidden(["xyz", "é", "yz"]);
+        assert!(rule.is_satisfied("xy zx"));
+        assert_eq!(
+            rule.check("aayzxyz"),
+            Err(RuleFailure {
+                rule: r#"none of ["xyz", "é", "yz"] (found "yz" at 2)"#.into()
+            })
+        );
+        assert!(!rule.is_satisfied("caféx"));
+    }
+
+    #[test]
+    fn composes_rules() {
+        let rule = min_vowels(1).and(letter_repeat(0).or(repeated_pair()));
+        assert!(rule.is_satisfied("aa"));
+        assert!(rule.is_satisfied("axyxy"));
+        assert!(!rule.is_satisfied("ab"));
+        assert!(!rule.is_satisfied("xx"));
+
+        assert!(min_vowels(1).not().is_satisfied("xyz"));
+        assert!(!min_vowels(1).not().is_satisfied("xaz"));
+    }
+
+    #[test]
+    fn reports_failing_rule() {
+        let rule = min_vowels(3)
+            .and(letter_repeat(0))
+            .and(letter_repeat(1).or(repeated_pair()));
+
+        assert_eq!(rule.check("aeiouuu"), Ok(()));
+        assert_eq!(
+            rule.check("aeiou").unwrap_err().rule,
+            "a letter repeating after a gap of 0"
+        );
+        assert_eq!(rule.check("xx").unwrap_err().rule, "at least 3 vowels");
+        assert_eq!(
+            rule.check("aeiuu").unwrap_err().rule,
+            "(a letter repeating after a gap of 1 or a non-overlapping repeated pair)"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+/// Values that solution parts can return as their answer.
+use grid_2d::Grid;
+
+/// An answer returned by a solution part.
+///
+/// The submission is what gets sent to Advent of Code, the rendering is what gets printed. These usually coincide,
+/// but differ for e.g. grids, which are rendered as a picture and submitted as the letters they show.
+pub trait Answer {
+    /// The string to submit, or `None` if the answer can't be submitted, e.g. a grid that doesn't show letters.
+    fn submission(&self) -> Option<String>;
+
+    /// The string to print. May span multiple lines. Defaults to the submission.
+    fn render(&self) -> String {
+        self.submission().unwrap_or_default()
+    }
+}
+
+impl<T: Answer + ?Sized> Answer for &T {
+    fn submission(&self) -> Option<String> {
+        (**self).submission()
+    }
+
+    fn render(&self) -> String {
+        (**self).render()
+    }
+}
+
+macro_rules! impl_answer_for_display {
+    ($($ty:ty),+) => {
+        $(
+            impl Answer for $ty {
+                fn submission(&self) -> Option<String> {
+                    Some(self.to_string())
+                }
+            }
+        )+
+    };
+}
+
+impl_answer_for_display!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, str, String
+);
+
+/// Tuples are submitted comma-separated, e.g. coordinates as `x,y`.
+impl<A: Answer, B: Answer> Answer for (A, B) {
+    fn submission(&self) -> Option<String> {
+        Some(format!("{},{}", self.0.submission()?, self.1.submission()?))
+    }
+}
+
+impl<A: Answer, B: Answer, C: Answer> Answer for (A, B, C) {
+    fn submission(&self) -> Option<String> {
+        Some(format!(
+            "{},{},{}",
+            self.0.submission()?,
+            self.1.submission()?,
+            self.2.submission()?
+        ))
+    }
+}
+
+/// Grids are rendered row by row. If they show block letters, those are submitted, see [`ocr`].
+/// Otherwise, they can't be submitted.
+impl Answer for Grid<char> {
+    fn submission(&self) -> Option<String> {
+        ocr(&self.render())
+    }
+
+    fn render(&self) -> String {
+        self.rows()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Boolean grids are rendered with `#` for `true` and `.` for `false`.
+impl Answer for Grid<bool> {
+    fn submission(&self) -> Option<String> {
+        ocr(&self.render())
+    }
+
+    fn render(&self) -> String {
+        self.rows()
+            .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+
+/// Block letters as drawn by puzzles, 4 pixels wide and 6 pixels high.
+const GLYPHS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// Reads block letters from ASCII art, e.g. a grid printed by a solution.
+///
+/// Letters are 4 pixels wide and 6 high, separated by an empty column. `#` and `█` are lit pixels,
+/// anything else is dark. Returns `None` if the art does not consist of known letters separated by empty columns.
+pub fn ocr(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+
+    if rows.len() != GLYPH_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let pixel = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+
+    let has_lit_separator = (GLYPH_WIDTH..width)
+        .step_by(GLYPH_WIDTH + 1)
+        .any(|x| (0..GLYPH_HEIGHT).any(|y| pixel(x, y)));
+    if has_lit_separator {
+        return None;
+    }
+
+    (0..width.div_ceil(GLYPH_WIDTH + 1))
+        .map(|index| {
+            let left = index * (GLYPH_WIDTH + 1);
+            let glyph: String = (0..GLYPH_HEIGHT)
+                .flat_map(|y| (left..left + GLYPH_WIDTH).map(move |x| (x, y)))
+                .map(|(x, y)| if pixel(x, y) { '#' } else { '.' })
+                .collect();
+
+            GLYPHS
+                .iter()
+                .find(|(_, pattern)| *pattern == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ocr, Answer, GLYPHS, GLYPH_WIDTH};
+    use grid_2d::{Grid, Size};
+
+    /// Draws letters the way a puzzle would.
+    fn draw(letters: &str) -> String {
+        (0..6)
+            .map(|y| {
+                letters
+                    .chars()
+                    .map(|letter| {
+                        let (_, pattern) = GLYPHS.iter().find(|(l, _)| *l == letter).unwrap();
+                        &pattern[y * GLYPH_WIDTH..(y + 1) * GLYPH_WIDTH]
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_letters() {
+        assert_eq!(ocr(&draw("ZEBRA")), Some("ZEBRA".into()));
+        assert_eq!(ocr(&draw("FLOCK")), Some("FLOCK".into()));
+        assert_eq!(ocr(&draw("GHJ").replace('#', "█")), Some("GHJ".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_art() {
+        assert_eq!(ocr("#"), None);
+        assert_eq!(ocr(&draw("AB").replace(".##.", "####")), None);
+    }
+
+    #[test]
+    fn rejects_lit_separators() {
+        let art = draw("LL")
+            .lines()
+            .map(|line| format!("{}#{}", &line[..GLYPH_WIDTH], &line[GLYPH_WIDTH + 1..]))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(ocr(&art), None);
+    }
+
+    #[test]
+    fn submits_scalars_and_tuples() {
+        assert_eq!(42u32.submission(), Some("42".into()));
+        assert_eq!("abc".submission(), Some("abc".into()));
+        assert_eq!((3, -4).submission(), Some("3,-4".into()));
+        assert_eq!((1usize, 2usize, 3usize).render(), "1,2,3");
+    }
+
+    #[test]
+    fn renders_and_submits_grids() {
+        let art = draw("HELLO");
+        let rows: Vec<Vec<char>> = art.lines().map(|l| l.chars().collect()).collect();
+        let grid = Grid::new_fn(Size::new(rows[0].len() as u32, 6), |coord| {
+            rows[coord.y as usize][coord.x as usize] == '#'
+        });
+
+        assert_eq!(grid.render(), art);
+        assert_eq!(grid.submission(), Some("HELLO".into()));
+
+        let small = Grid::new_copy(Size::new(2, 1), 'x');
+        assert_eq!(small.render(), "xx");
+        assert_eq!(small.submission(), None);
+    }
+}
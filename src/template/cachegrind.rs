@@ -0,0 +1,286 @@
+/// Wrapper module around valgrind's cachegrind tool, used by `cargo time --instructions`.
+///
+/// Wall-clock timings vary between machines and runs. Instruction counts and simulated cache misses don't, so they
+/// can be compared across commits. Each part is measured by running the solution once with only that part, minus a
+/// baseline run without any part, which covers starting the binary and reading the input.
+///
+/// The counts of solutions using `HashMap` or `HashSet` with the default, randomly seeded hasher still vary slightly.
+use std::{
+    env,
+    fmt::Display,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+use crate::template::{runner::INPUT_MISSING, Day};
+
+#[derive(Debug)]
+pub enum CachegrindError {
+    CommandNotFound,
+    BuildFailed,
+    InputMissing,
+    BadExitStatus(Output),
+    UnexpectedOutput,
+}
+
+impl Display for CachegrindError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CachegrindError::CommandNotFound => {
+                write!(f, "valgrind is not present in environment.")
+            }
+            CachegrindError::BuildFailed => write!(f, "the solution could not be built."),
+            CachegrindError::InputMissing => write!(f, "the input of the day is missing."),
+            CachegrindError::BadExitStatus(_) => {
+                write!(
+                    f,
+                    "the solution exited with a non-zero status under valgrind."
+                )
+            }
+            CachegrindError::UnexpectedOutput => {
+                write!(
+                    f,
+                    "could not find the cachegrind summary in valgrind's output."
+                )
+            }
+        }
+    }
+}
+
+/// Instruction count and cache misses of a single run of a part, as simulated by cachegrind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstructionCounts {
+    /// Instructions executed (`I refs`).
+    pub instructions: u64,
+    /// First-level cache misses for instructions and data (`I1 misses` + `D1 misses`).
+    pub l1_misses: u64,
+    /// Last-level cache misses (`LL misses`).
+    pub ll_misses: u64,
+}
+
+impl InstructionCounts {
+    /// The counts of `self` that are not part of `baseline`.
+    #[must_use]
+    pub fn without(&self, baseline: &Self) -> Self {
+        Self {
+            instructions: self.instructions.saturating_sub(baseline.instructions),
+            l1_misses: self.l1_misses.saturating_sub(baseline.l1_misses),
+            ll_misses: self.ll_misses.saturating_sub(baseline.ll_misses),
+        }
+    }
+}
+
+/// Formats a number with thousands separators, like cachegrind does.
+fn format_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+
+    formatted
+}
+
+impl Display for InstructionCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} instructions, {} L1 misses, {} LL misses",
+            format_count(self.instructions),
+            format_count(self.l1_misses),
+            format_count(self.ll_misses)
+        )
+    }
+}
+
+pub fn check() -> Result<(), CachegrindError> {
+    Command::new("valgrind")
+        .arg("--version")
+        .output()
+        .map_err(|_| CachegrindError::CommandNotFound)?;
+    Ok(())
+}
+
+/// Builds the optimized binary of a day.
+pub fn build(day: Day) -> Result<(), CachegrindError> {
+    let status = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--bin", &day.to_string()])
+        .status()
+        .map_err(|_| CachegrindError::BuildFailed)?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CachegrindError::BuildFailed)
+    }
+}
+
+fn get_bin_path(day: Day) -> PathBuf {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    PathBuf::from(target_dir)
+        .join("release")
+        .join(day.to_string())
+}
+
+/// Runs the binary of a day built by [`build`] under cachegrind. `part` selects the part to run, `0` runs none.
+/// Also returns the output of the solution.
+pub fn run(day: Day, part: u8) -> Result<(InstructionCounts, String), CachegrindError> {
+    let output = Command::new("valgrind")
+        .args([
+            "--tool=cachegrind",
+            "--cache-sim=yes",
+            "--cachegrind-out-file=/dev/null",
+        ])
+        .arg(get_bin_path(day))
+        .args(["--part", &part.to_string()])
+        .output()
+        .map_err(|_| CachegrindError::CommandNotFound)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if stdout.starts_with(INPUT_MISSING) {
+        return Err(CachegrindError::InputMissing);
+    }
+
+    if !output.status.success() {
+        return Err(CachegrindError::BadExitStatus(output));
+    }
+
+    let counts = parse_summary(&String::from_utf8_lossy(&output.stderr))
+        .ok_or(CachegrindError::UnexpectedOutput)?;
+    Ok((counts, stdout))
+}
+
+/// Whether the output of a solution shows an answer for `part`. Parts that are not declared print nothing, parts
+/// without an answer or with an error print `✖`.
+fn has_answer(stdout: &str, part: u8) -> bool {
+    let prefix = format!("Part {part}:");
+
+    // NOTE: intermediate output is overwritten with `\r`, only the last segment is the final result.
+    stdout
+        .lines()
+        .filter_map(|line| line.split('\r').next_back())
+        .any(|line| line.starts_with(&prefix) && !line.contains('✖'))
+}
+
+/// Measures both parts of a day, see the module docs. Parts without an answer have no counts, e.g. day 25 has no
+/// part two.
+pub fn measure(day: Day) -> Result<[Option<InstructionCounts>; 2], CachegrindError> {
+    let (baseline, _) = run(day, 0)?;
+    let part = |part: u8| -> Result<Option<InstructionCounts>, CachegrindError> {
+        let (counts, stdout) = run(day, part)?;
+        Ok(has_answer(&stdout, part).then(|| counts.without(&baseline)))
+    };
+
+    Ok([part(1)?, part(2)?])
+}
+
+/// Parses the summary that cachegrind prints to stderr, e.g. `==31751== I   refs:      27,742,716`.
+fn parse_summary(stderr: &str) -> Option<InstructionCounts> {
+    let count = |label: &str| {
+        stderr.lines().find_map(|line| {
+            let (key, value) = line.split("== ").nth(1)?.split_once(':')?;
+            if key.split_whitespace().collect::<Vec<_>>().join(" ") != label {
+                return None;
+            }
+            value
+                .split_whitespace()
+                .next()?
+                .replace(',', "")
+                .parse::<u64>()
+                .ok()
+        })
+    };
+
+    Some(InstructionCounts {
+        instructions: count("I refs")?,
+        l1_misses: count("I1 misses")? + count("D1 misses")?,
+        ll_misses: count("LL misses")?,
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{has_answer, parse_summary, InstructionCounts};
+
+    const SUMMARY: &str = "==31751== Cachegrind, a high-precision tracing profiler
+==31751== Command: target/release/01 --part 1
+==31751==
+Part 1: 74
+==31751==
+==31751== I   refs:      27,742,716
+==31751== I1  misses:           276
+==31751== LLi misses:           275
+==31751== I1  miss rate:       0.00%
+==31751== LLi miss rate:       0.00%
+==31751==
+==31751== D   refs:      15,430,290  (10,955,517 rd   + 4,474,773 wr)
+==31751== D1  misses:        41,185  (    21,905 rd   +    19,280 wr)
+==31751== LLd misses:        23,085  (     3,640 rd   +    19,445 wr)
+==31751==
+==31751== LL refs:           41,461  (    22,181 rd   +    19,280 wr)
+==31751== LL misses:         23,360  (     3,915 rd   +    19,445 wr)
+==31751== LL miss rate:         0.0% (       0.0%     +       0.4%  )
+";
+
+    #[test]
+    fn parses_summary() {
+        assert_eq!(
+            parse_summary(SUMMARY),
+            Some(InstructionCounts {
+                instructions: 27_742_716,
+                l1_misses: 41_461,
+                ll_misses: 23_360,
+            })
+        );
+        assert_eq!(parse_summary("==1== I   refs:  10"), None);
+    }
+
+    #[test]
+    fn subtracts_baseline() {
+        let part = InstructionCounts {
+            instructions: 1_500,
+            l1_misses: 20,
+            ll_misses: 5,
+        };
+        let baseline = InstructionCounts {
+            instructions: 1_000,
+            l1_misses: 30,
+            ll_misses: 5,
+        };
+
+        assert_eq!(
+            part.without(&baseline),
+            InstructionCounts {
+                instructions: 500,
+                l1_misses: 0,
+                ll_misses: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn formats_counts() {
+        let counts = InstructionCounts {
+            instructions: 27_742_716,
+            l1_misses: 276,
+            ll_misses: 1_000,
+        };
+        assert_eq!(
+            counts.to_string(),
+            "27,742,716 instructions, 276 L1 misses, 1,000 LL misses"
+        );
+    }
+
+    #[test]
+    fn detects_answers() {
+        let stdout = "Part 1: \x1b[1m74\x1b[0m (1.2ms)\nPart 2: ✖ \rPart 2: ✖             \n";
+        assert!(has_answer(stdout, 1));
+        assert!(!has_answer(stdout, 2));
+        assert!(!has_answer("Part 1: 74 (1.2ms)\n", 2));
+        assert!(!has_answer("Part 1: ✖ error\n  invalid input\n", 1));
+    }
+}
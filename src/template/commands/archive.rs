@@ -0,0 +1,85 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{readme_benchmarks, timings::Timings, BinLayout, Day};
+
+/// The file or directory holding a day's solution.
+fn get_source_path(day: Day, layout: BinLayout) -> PathBuf {
+    match layout {
+        BinLayout::File => PathBuf::from(layout.path(day)),
+        BinLayout::Dir => PathBuf::from(format!("src/bin/{day}")),
+    }
+}
+
+/// Where an archived solution is moved to, e.g. `src/bin/09_v1.rs`.
+/// Archived solutions stay in `src/bin`, so they remain binaries that can be run and tested.
+fn get_archive_path(day: Day, layout: BinLayout, version: u32) -> PathBuf {
+    match layout {
+        BinLayout::File => PathBuf::from(format!("src/bin/{day}_v{version}.rs")),
+        BinLayout::Dir => PathBuf::from(format!("src/bin/{day}_v{version}")),
+    }
+}
+
+/// The first version that has not been archived yet, in either layout.
+fn next_version(day: Day) -> u32 {
+    (1..)
+        .find(|&version| {
+            [BinLayout::File, BinLayout::Dir]
+                .into_iter()
+                .all(|layout| !get_archive_path(day, layout, version).exists())
+        })
+        .unwrap()
+}
+
+/// Moves the solution of a day to the next free version. Returns the name of the archived binary.
+fn archive(day: Day) -> Result<String, String> {
+    let layout =
+        BinLayout::detect(day).ok_or_else(|| format!("there is no solution for day {day}"))?;
+    let version = next_version(day);
+
+    let source = get_source_path(day, layout);
+    let target = get_archive_path(day, layout, version);
+    fs::rename(&source, &target)
+        .map_err(|e| format!("could not move \"{}\": {e}", source.display()))?;
+
+    println!(
+        "Archived \"{}\" as \"{}\"",
+        source.display(),
+        target.display()
+    );
+    Ok(format!("{day}_v{version}"))
+}
+
+/// Removes the day from `timings.json` and the readme, as its timings belong to the archived solution.
+fn remove_timings(day: Day) -> Result<(), String> {
+    let timings = Timings::read_from_file();
+    if !timings.has_day(day) {
+        return Ok(());
+    }
+
+    let timings = timings.without(day);
+    timings
+        .store_file()
+        .map_err(|e| format!("could not store timings: {e}"))?;
+    readme_benchmarks::update(timings).map_err(|e| format!("could not update readme: {e:?}"))?;
+
+    println!("Removed timings of day {day}");
+    Ok(())
+}
+
+pub fn handle(day: Day) {
+    let bin = match archive(day) {
+        Ok(bin) => bin,
+        Err(e) => {
+            eprintln!("Failed to archive solution: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = remove_timings(day) {
+        eprintln!("Failed to remove timings: {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo run --release --bin {bin}` to run the archived solution.");
+}
@@ -1,5 +1,5 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_cli, commands::scaffold, get_generated_marker_path, Day};
+use std::{fs, process};
 
 pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
@@ -11,4 +11,14 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    // the downloaded input replaces one created by `cargo generate-input`.
+    let _ = fs::remove_file(get_generated_marker_path(day));
+
+    // keep the docs of an existing solution in sync, e.g. once part two has been unlocked.
+    if scaffold::module_exists(day) {
+        if let Err(e) = scaffold::refresh_docs(day, false) {
+            eprintln!("Failed to refresh docs: {e}");
+        }
+    }
 }
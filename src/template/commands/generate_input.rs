@@ -0,0 +1,42 @@
+use std::process::{self, Command, Stdio};
+
+use crate::template::{BinLayout, Day};
+
+/// Writes a synthetic input for a day by running its solution with `--generate`, see `solution!`.
+pub fn handle(day: Day, seed: Option<u64>, size: Option<usize>, overwrite: bool) {
+    if BinLayout::detect(day).is_none() {
+        eprintln!("Failed to generate input: there is no solution for day {day}");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--generate".to_string(),
+    ];
+
+    if let Some(seed) = seed {
+        cmd_args.extend(["--seed".to_string(), seed.to_string()]);
+    }
+
+    if let Some(size) = size {
+        cmd_args.extend(["--size".to_string(), size.to_string()]);
+    }
+
+    if overwrite {
+        cmd_args.push("--overwrite".to_string());
+    }
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if !status.is_ok_and(|status| status.success()) {
+        process::exit(1);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod all;
+pub mod archive;
 pub mod download;
+pub mod generate_input;
 pub mod read;
+pub mod reset;
 pub mod scaffold;
 pub mod solve;
+pub mod test_all;
 pub mod time;
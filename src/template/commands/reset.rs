@@ -0,0 +1,30 @@
+use std::process;
+
+use crate::template::commands::{archive, scaffold};
+use crate::template::{BinLayout, Day};
+
+/// Archives the solution of a day and scaffolds a fresh one in its place.
+/// Without a template, the new solution keeps the layout of the archived one.
+pub fn handle(day: Day, template: Option<&str>, dir: bool) {
+    let Some(layout) = BinLayout::detect(day) else {
+        eprintln!("Failed to reset: there is no solution for day {day}");
+        process::exit(1);
+    };
+
+    // load the template first, so that an unknown one fails before the solution is archived.
+    let template = if dir || (template.is_none() && layout == BinLayout::Dir) {
+        None
+    } else {
+        Some(scaffold::load_template_or_exit(
+            template.unwrap_or(scaffold::DEFAULT_TEMPLATE),
+        ))
+    };
+
+    archive::handle(day);
+    println!();
+
+    match template {
+        Some(template) => scaffold::handle_template(day, false, &template),
+        None => scaffold::handle_dir(day, false),
+    }
+}
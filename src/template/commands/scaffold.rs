@@ -1,13 +1,198 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::{aoc_cli, puzzle, BinLayout, Day};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Templates that ship with the repository, see `src/templates`.
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "default",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/default.txt"
+        )),
+    ),
+    (
+        "nom-parser",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/nom-parser.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "shared-parse",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/shared-parse.txt"
+        )),
+    ),
+];
+
+/// Templates for solutions that are split into modules, see `--dir`. Files are created in `src/bin/<day>/`.
+const DIR_TEMPLATE: [(&str, &str); 3] = [
+    (
+        "main.rs",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/dir/main.txt"
+        )),
+    ),
+    (
+        "parse.rs",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/dir/parse.txt"
+        )),
+    ),
+    (
+        "solve.rs",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/dir/solve.txt"
+        )),
+    ),
+];
+
+/// Directory for user-defined templates. A template `<name>.txt` in here takes precedence over a built-in one.
+const USER_TEMPLATE_DIR: &str = "templates";
+
+fn get_user_template_path(name: &str) -> PathBuf {
+    PathBuf::from(USER_TEMPLATE_DIR).join(format!("{name}.txt"))
+}
+
+/// Names of all templates, built-in and user-defined.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(USER_TEMPLATE_DIR) {
+        for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(name) = path.file_stem() {
+                    names.push(name.to_string_lossy().into());
+                }
+            }
+        }
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn load_template(name: &str) -> Result<String, String> {
+    let path = get_user_template_path(name);
+
+    if path.exists() {
+        return fs::read_to_string(&path)
+            .map_err(|e| format!("could not read template \"{}\": {e}", path.display()));
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| template.to_string())
+        .ok_or_else(|| {
+            format!(
+                "unknown template \"{name}\", available templates: {}",
+                available_templates().join(", ")
+            )
+        })
+}
+
+/// Replaces the placeholders a template can use:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `9`.
+///  - `%DAY%`: the day with padding, e.g. `09`.
+///  - `%YEAR%`: the year set in `AOC_YEAR`.
+///  - `%TITLE%`: the puzzle heading, e.g. `Day 9: All in a Single Night`, or just `Day 9` if the puzzle has not been
+///    downloaded yet.
+///  - `%URL%`: the link to the puzzle.
+fn fill_placeholders(
+    template: &str,
+    day: Day,
+    year: Option<u16>,
+    title: &str,
+    url: &str,
+) -> String {
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace(
+            "%YEAR%",
+            &year.map(|year| year.to_string()).unwrap_or_default(),
+        )
+        .replace("%TITLE%", title)
+        .replace("%URL%", url)
+}
+
+/// Fills in the placeholders of a template for a day, using the downloaded puzzle if there is one.
+fn fill_template(template: &str, day: Day) -> String {
+    let title = puzzle::read(day).map(|puzzle| puzzle.title);
+    fill_placeholders(
+        template,
+        day,
+        aoc_cli::get_year(),
+        &puzzle::heading(day, title.as_deref()),
+        &puzzle::get_url(day),
+    )
+}
+
+/// Replaces the module docs of a solution with the title and summary of its downloaded puzzle.
+/// Unless `overwrite` is set, docs that were written by hand are kept, see `puzzle::has_generated_docs`.
+pub fn refresh_docs(day: Day, overwrite: bool) -> Result<(), String> {
+    let module_path = get_module_path(day);
+    let puzzle = puzzle::read(day).ok_or_else(|| {
+        format!("puzzle for day {day} has not been downloaded, run `cargo download {day}`")
+    })?;
+    let source = fs::read_to_string(&module_path)
+        .map_err(|e| format!("could not read \"{module_path}\": {e}"))?;
+
+    let url = puzzle::get_url(day);
+    if !overwrite && !puzzle::has_generated_docs(&source, day, &puzzle, &url) {
+        println!(
+            "Kept the docs of \"{module_path}\", run `cargo scaffold {day} --refresh-docs` to replace them."
+        );
+        return Ok(());
+    }
+
+    let docs = puzzle::render_docs(day, &puzzle, &url);
+    fs::write(&module_path, puzzle::replace_docs(&source, &docs))
+        .map_err(|e| format!("could not write \"{module_path}\": {e}"))?;
+
+    println!("Updated docs of \"{module_path}\"");
+    Ok(())
+}
+
+pub fn handle_refresh_docs(day: Day) {
+    if let Err(e) = refresh_docs(day, true) {
+        eprintln!("Failed to refresh docs: {e}");
+        process::exit(1);
+    }
+}
+
+fn get_module_path(day: Day) -> String {
+    BinLayout::detect(day).unwrap_or(BinLayout::File).path(day)
+}
+
+/// Whether a solution has been scaffolded for the day.
+pub fn module_exists(day: Day) -> bool {
+    BinLayout::detect(day).is_some()
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,6 +204,10 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
+fn has_contents(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
 fn create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
@@ -27,53 +216,148 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day, overwrite: bool, template: &str) {
+    handle_template(day, overwrite, &load_template_or_exit(template));
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+/// Loads a template by name, exiting with an error if it is unknown or unreadable.
+pub fn load_template_or_exit(name: &str) -> String {
+    match load_template(name) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
-
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
     }
+}
+
+/// Scaffolds a single-file solution from the contents of a template, see `load_template_or_exit`.
+pub fn handle_template(day: Day, overwrite: bool, template: &str) {
+    let module_path = BinLayout::File.path(day);
+    scaffold(
+        day,
+        BinLayout::File,
+        &[(module_path, fill_template(template, day))],
+        overwrite,
+    );
+}
+
+/// Scaffolds a solution split into `main.rs`, `parse.rs` and `solve.rs`.
+pub fn handle_dir(day: Day, overwrite: bool) {
+    let files: Vec<(String, String)> = DIR_TEMPLATE
+        .iter()
+        .map(|(name, template)| {
+            (
+                format!("src/bin/{day}/{name}"),
+                fill_template(template, day),
+            )
+        })
+        .collect();
+
+    scaffold(day, BinLayout::Dir, &files, overwrite);
+}
+
+fn scaffold(day: Day, layout: BinLayout, files: &[(String, String)], overwrite: bool) {
+    // cargo refuses to build two binaries with the same name, so a day can only use one layout.
+    if let Some(existing) = BinLayout::detect(day).filter(|existing| *existing != layout) {
+        eprintln!(
+            "Failed to create module file: day {day} already exists as \"{}\"",
+            existing.path(day)
+        );
+        process::exit(1);
+    }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    for (module_path, contents) in files {
+        if let Some(dir) = Path::new(module_path).parent() {
+            if let Err(e) = fs::create_dir_all(dir) {
+                eprintln!("Failed to create module directory: {e}");
+                process::exit(1);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+
+        let mut file = match safe_create_file(module_path, overwrite) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Failed to create module file: {e}");
+                process::exit(1);
+            }
+        };
+
+        match file.write_all(contents.as_bytes()) {
+            Ok(()) => {
+                println!("Created module file \"{}\"", &module_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to write module contents: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    let input_path = format!("data/inputs/{day}.txt");
+    let example_path = format!("data/examples/{day}.txt");
+
+    // inputs and examples are kept when re-scaffolding, e.g. after `cargo reset`, unless overwriting.
+    for (kind, path) in [("input", &input_path), ("example", &example_path)] {
+        if !overwrite && has_contents(path) {
+            println!("Kept existing {kind} file \"{path}\"");
+            continue;
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+
+        match create_file(path) {
+            Ok(_) => {
+                println!("Created empty {kind} file \"{path}\"");
+            }
+            Err(e) => {
+                eprintln!("Failed to create {kind} file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_placeholders, load_template, BUILTIN_TEMPLATES, DIR_TEMPLATE};
+    use crate::day;
+
+    #[test]
+    fn fills_placeholders() {
+        let template = "//! # %TITLE% (%YEAR%)\n//! <%URL%>\nsolution!(%DAY_NUMBER%); // %DAY%";
+
+        assert_eq!(
+            fill_placeholders(
+                template,
+                day!(9),
+                Some(2015),
+                "Day 9: All in a Single Night",
+                "https://adventofcode.com/2015/day/9"
+            ),
+            "//! # Day 9: All in a Single Night (2015)\n//! <https://adventofcode.com/2015/day/9>\nsolution!(9); // 09"
+        );
+        assert_eq!(fill_placeholders("%YEAR%", day!(9), None, "Day 9", ""), "");
+    }
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, template) in BUILTIN_TEMPLATES {
+            assert_eq!(load_template(name).as_deref(), Ok(template));
+            assert!(template.contains("%DAY_NUMBER%"));
+        }
+        assert!(load_template("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn dir_template_declares_its_modules() {
+        let (name, main) = DIR_TEMPLATE[0];
+        assert_eq!(name, "main.rs");
+
+        for (name, _) in &DIR_TEMPLATE[1..] {
+            let module = name.trim_end_matches(".rs");
+            assert!(main.contains(&format!("mod {module};")));
+        }
+    }
+}
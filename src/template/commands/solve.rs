@@ -1,21 +1,58 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Day, InputSource};
+
+/// Flags of the `solve` command that are passed on to the solution.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub release: bool,
+    pub dhat: bool,
+    pub explain: bool,
+    /// Run and compare all variants of a part, see `solution!`.
+    pub variants: bool,
+    pub submit_part: Option<u8>,
+    /// `0` for no logs, `1` for `-v` and `2` for `-vv`.
+    pub verbosity: u8,
+    /// Write logs to `data/logs/<day>.log` instead of stderr.
+    pub log_file: bool,
+    pub params: Vec<String>,
+    pub input_source: InputSource,
+}
+
+pub fn handle(day: Day, options: &Options) {
+    let Options {
+        release,
+        dhat,
+        explain,
+        variants,
+        submit_part,
+        verbosity,
+        log_file,
+        params,
+        input_source,
+    } = options;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
+    let mut features = vec![];
+
+    if *dhat {
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
+    } else if *release {
         cmd_args.push("--release".to_string());
     }
 
+    // logging is compiled out of optimized builds unless requested.
+    if *verbosity > 0 && (*dhat || *release) {
+        features.push("trace");
+    }
+
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +60,31 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if *explain {
+        cmd_args.push("--explain".to_string());
+    }
+
+    if *variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    match verbosity {
+        0 => {}
+        1 => cmd_args.push("-v".to_string()),
+        _ => cmd_args.push("-vv".to_string()),
+    }
+
+    if *log_file {
+        cmd_args.push("--log".to_string());
+    }
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    cmd_args.extend(input_source.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,248 @@
+/// Runs the tests of every scaffolded day and prints a summary, see `cargo test-all`.
+///
+/// Each day is its own binary, so `cargo test` prints its results in many separate blocks. This runs the tests day
+/// by day and collects them in a single table. Slow tests are named `slow_*` and marked `#[ignore = "slow"]`, they
+/// only run with `--slow`. Other ignored tests never run.
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::process::{self, Command, Output};
+
+use crate::template::{all_days, BinLayout, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The results of the tests of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    /// Names of the tests that failed, e.g. `tests::test_part_one`.
+    pub failures: Vec<String>,
+}
+
+impl TestSummary {
+    /// Parses the output of a test binary. Returns `None` if no tests ran, e.g. because the day doesn't compile.
+    pub fn parse(output: &str) -> Option<Self> {
+        let mut summary = Self::default();
+        let mut has_result = false;
+
+        for line in output.lines() {
+            if let Some(result) = line.strip_prefix("test result: ") {
+                has_result = true;
+                summary.passed += count(result, "passed");
+                summary.failed += count(result, "failed");
+                summary.ignored += count(result, "ignored");
+            } else if let Some(name) = line
+                .strip_prefix("test ")
+                .and_then(|line| line.strip_suffix(" ... FAILED"))
+            {
+                summary.failures.push(name.to_string());
+            }
+        }
+
+        has_result.then_some(summary)
+    }
+}
+
+/// The number in front of `label` in a line like `ok. 2 passed; 0 failed; 1 ignored; ...`.
+fn count(result: &str, label: &str) -> usize {
+    result
+        .split(['.', ';'])
+        .find_map(|field| field.trim().strip_suffix(label)?.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Prefix of the names of slow tests, see the module docs.
+const SLOW_PREFIX: &str = "slow_";
+
+/// Why the tests of a day did not report any results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TestError {
+    DoesNotCompile,
+    /// The test binary exited without printing results, e.g. because it aborted.
+    Aborted(String),
+}
+
+impl Display for TestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestError::DoesNotCompile => write!(f, "does not compile"),
+            TestError::Aborted(status) => write!(f, "aborted ({status})"),
+        }
+    }
+}
+
+fn cargo_test(day: Day, test_args: &[&str]) -> Result<TestSummary, TestError> {
+    let day = day.to_string();
+    let output = Command::new("cargo")
+        .args(["test", "--bin", &day, "--"])
+        .args(test_args)
+        .output()
+        .map_err(|e| TestError::Aborted(e.to_string()))?;
+
+    TestSummary::parse(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| classify(&output))
+}
+
+fn classify(output: &Output) -> TestError {
+    if String::from_utf8_lossy(&output.stderr).contains("error: could not compile") {
+        TestError::DoesNotCompile
+    } else {
+        TestError::Aborted(output.status.to_string())
+    }
+}
+
+/// Runs the tests of a day. With `slow`, the ignored tests named `slow_*` run as well.
+fn run_tests(day: Day, slow: bool) -> Result<TestSummary, TestError> {
+    let mut summary = cargo_test(day, &[])?;
+
+    if slow && summary.ignored > 0 {
+        let slow_summary = cargo_test(day, &["--ignored", SLOW_PREFIX])?;
+        let ran = slow_summary.passed + slow_summary.failed;
+
+        summary.passed += slow_summary.passed;
+        summary.failed += slow_summary.failed;
+        summary.ignored = summary.ignored.saturating_sub(ran);
+        summary.failures.extend(slow_summary.failures);
+    }
+
+    Ok(summary)
+}
+
+pub fn handle(slow: bool) {
+    let days: Vec<Day> = all_days()
+        .filter(|day| BinLayout::detect(*day).is_some())
+        .collect();
+
+    if days.is_empty() {
+        println!("No days have been scaffolded yet.");
+        return;
+    }
+
+    println!(
+        "{ANSI_BOLD}Day  {:>7}  {:>7}  {:>7}{ANSI_RESET}",
+        "passed", "failed", "ignored"
+    );
+
+    let mut total = TestSummary::default();
+    let mut broken: Vec<(Day, TestError)> = vec![];
+
+    for day in days {
+        print!("{day}   {ANSI_ITALIC}testing{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        match run_tests(day, slow) {
+            Ok(summary) => {
+                let status = if summary.failed > 0 { "✖" } else { "✔" };
+                println!(
+                    "\r{day}   {:>7}  {:>7}  {:>7}  {status}",
+                    summary.passed, summary.failed, summary.ignored
+                );
+
+                total.passed += summary.passed;
+                total.failed += summary.failed;
+                total.ignored += summary.ignored;
+                total
+                    .failures
+                    .extend(summary.failures.iter().map(|name| format!("{day} {name}")));
+            }
+            Err(e) => {
+                println!("\r{day}   ✖ {e}");
+                broken.push((day, e));
+            }
+        }
+    }
+
+    println!(
+        "{ANSI_BOLD}All  {:>7}  {:>7}  {:>7}{ANSI_RESET}",
+        total.passed, total.failed, total.ignored
+    );
+
+    if !total.failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for failure in &total.failures {
+            println!("  {failure}");
+        }
+    }
+
+    if !broken.is_empty() {
+        let days = broken
+            .iter()
+            .map(|(day, _)| day.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "\n{ANSI_BOLD}Not run:{ANSI_RESET} {days}, run `cargo test --bin <day>` for details."
+        );
+    }
+
+    if total.ignored > 0 && !slow {
+        println!(
+            "\nIgnored tests may include slow ones, run `cargo test-all --slow` to include them."
+        );
+    }
+
+    if total.failed > 0 || !broken.is_empty() {
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::process::{ExitStatus, Output};
+
+    use super::{classify, TestError, TestSummary};
+
+    const OUTPUT: &str = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_slow ... ignored, slow
+
+failures:
+
+---- tests::test_part_two stdout ----
+assertion failed
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    #[test]
+    fn parses_test_output() {
+        assert_eq!(
+            TestSummary::parse(OUTPUT),
+            Some(TestSummary {
+                passed: 1,
+                failed: 1,
+                ignored: 1,
+                failures: vec!["tests::test_part_two".into()],
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_output_without_results() {
+        assert_eq!(TestSummary::parse("error[E0425]: cannot find value"), None);
+    }
+
+    #[test]
+    fn tells_compile_errors_from_aborts() {
+        let output = |stderr: &str| Output {
+            status: ExitStatus::default(),
+            stdout: vec![],
+            stderr: stderr.as_bytes().to_vec(),
+        };
+
+        assert_eq!(
+            classify(&output(
+                "error: could not compile `advent_of_code` (bin \"04\" test)"
+            )),
+            TestError::DoesNotCompile
+        );
+        assert!(matches!(
+            classify(&output("thread 'main' has overflowed its stack")),
+            TestError::Aborted(_)
+        ));
+    }
+}
@@ -1,8 +1,14 @@
 use std::collections::HashSet;
+use std::io::{stdout, Write};
+use std::process::{self, Command, Stdio};
 
+use crate::template::cachegrind::{self, CachegrindError};
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    all_days, is_input_generated, readme_benchmarks, BinLayout, Day, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET,
+};
 
 pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
@@ -24,7 +30,7 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let timings = run_multi(&days_to_run, true, true).unwrap();
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
+        let merged_timings = stored_timings.merge(&without_generated_inputs(timings));
         merged_timings.store_file().unwrap();
 
         println!();
@@ -38,3 +44,132 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Drops the timings of days whose puzzle input was generated, they are never stored.
+fn without_generated_inputs(timings: Timings) -> Timings {
+    let (generated, data): (Vec<Timing>, Vec<Timing>) = timings
+        .data
+        .into_iter()
+        .partition(|timing| is_input_generated(timing.day));
+
+    for timing in generated {
+        println!(
+            "Not storing timings of day {}, its input is generated. Run `cargo download {}` to replace it.",
+            timing.day, timing.day
+        );
+    }
+
+    Timings { data }
+}
+
+/// Runs the parts of a day on inputs of growing size and reports how their timings scale, see `scaling`.
+pub fn handle_scaling(day: Day) {
+    if BinLayout::detect(day).is_none() {
+        eprintln!("Failed to measure scaling: there is no solution for day {day}");
+        process::exit(1);
+    }
+
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day.to_string(),
+            "--",
+            "--scaling",
+        ])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if !status.is_ok_and(|status| status.success()) {
+        process::exit(1);
+    }
+}
+
+/// Counts the instructions and cache misses of each part with cachegrind, see `cachegrind`.
+/// Like `handle`, days that have been measured before are skipped unless a day or `--all` is passed.
+pub fn handle_instructions(day: Option<Day>, run_all: bool, store: bool) {
+    if let Err(e) = cachegrind::check() {
+        eprintln!("Failed to count instructions: {e}");
+        eprintln!("`--instructions` requires valgrind, see https://valgrind.org/downloads/.");
+        process::exit(1);
+    }
+
+    let stored_timings = Timings::read_from_file();
+
+    let mut days_to_run: Vec<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| BinLayout::detect(*day).is_some())
+                .filter(|day| {
+                    run_all
+                        || !stored_timings
+                            .data
+                            .iter()
+                            .any(|t| t.day == *day && t.instructions_1.is_some())
+                })
+                .collect()
+        },
+        |day| vec![day],
+    );
+    days_to_run.sort_unstable();
+
+    let mut timings = Timings::default();
+
+    for day in days_to_run {
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+        print!("{ANSI_ITALIC}Running under cachegrind…{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let counts = cachegrind::build(day).and_then(|()| cachegrind::measure(day));
+        print!("\r");
+
+        match counts {
+            Ok([part_1, part_2]) => {
+                for (part, counts) in [(1, part_1), (2, part_2)] {
+                    match counts {
+                        Some(counts) => println!("Part {part}: {counts}"),
+                        None => println!("Part {part}: ✖ no answer"),
+                    }
+                }
+
+                let stored = stored_timings.data.iter().find(|t| t.day == day);
+                timings.data.push(Timing {
+                    instructions_1: part_1,
+                    instructions_2: part_2,
+                    ..stored.cloned().unwrap_or_else(|| Timing {
+                        day,
+                        ..Default::default()
+                    })
+                });
+            }
+            Err(CachegrindError::InputMissing) => {
+                println!("No input found for day {day}, run `cargo download {day}`.");
+            }
+            Err(e) => {
+                println!("Failed to count instructions: {e}");
+                if let CachegrindError::BadExitStatus(output) = e {
+                    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                }
+            }
+        }
+
+        println!();
+    }
+
+    if store {
+        let timings = without_generated_inputs(timings);
+        if timings.data.is_empty() {
+            return;
+        }
+
+        let merged_timings = stored_timings.merge(&timings);
+        match merged_timings.store_file() {
+            Ok(()) => println!("Stored updated instruction counts."),
+            Err(_) => eprintln!("Failed to store updated instruction counts."),
+        }
+    }
+}
@@ -21,6 +21,13 @@ const SERVER_UTC_OFFSET: i32 = -5;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Day(u8);
 
+impl Default for Day {
+    /// The first day of advent.
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
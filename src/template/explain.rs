@@ -0,0 +1,93 @@
+/// An optional channel for solutions to attach details to their answers, e.g. the route behind a distance.
+///
+/// Details are only recorded while the runner captures them for `--explain`. It does so in an extra run after the
+/// timed ones, so explaining never affects timings. Outside of that run, [`detail`] does not evaluate its value.
+use std::cell::RefCell;
+use std::fmt::Display;
+
+/// A named detail recorded by a solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detail {
+    pub key: String,
+    pub value: String,
+}
+
+thread_local! {
+    static DETAILS: RefCell<Option<Vec<Detail>>> = const { RefCell::new(None) };
+}
+
+/// Whether details are currently being recorded.
+/// Use this to skip work that is only needed for explanations.
+pub fn is_enabled() -> bool {
+    DETAILS.with(|details| details.borrow().is_some())
+}
+
+/// Records a detail for the current part. `value` is only evaluated when explaining.
+pub fn detail<T: Display>(key: &str, value: impl FnOnce() -> T) {
+    if !is_enabled() {
+        return;
+    }
+
+    let detail = Detail {
+        key: key.to_string(),
+        value: value().to_string(),
+    };
+
+    DETAILS.with(|details| {
+        if let Some(details) = details.borrow_mut().as_mut() {
+            details.push(detail);
+        }
+    });
+}
+
+/// Runs `func`, returning its result along with all details it recorded.
+pub fn capture<T>(func: impl FnOnce() -> T) -> (T, Vec<Detail>) {
+    DETAILS.with(|details| *details.borrow_mut() = Some(vec![]));
+    let result = func();
+    let details = DETAILS.with(|details| details.borrow_mut().take().unwrap_or_default());
+
+    (result, details)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, detail, is_enabled, Detail};
+
+    #[test]
+    fn ignores_details_outside_of_capture() {
+        let mut evaluated = false;
+        detail("route", || {
+            evaluated = true;
+            "a -> b"
+        });
+
+        assert!(!evaluated);
+        assert!(!is_enabled());
+    }
+
+    #[test]
+    fn captures_details() {
+        let (result, details) = capture(|| {
+            assert!(is_enabled());
+            detail("route", || "a -> b");
+            detail("length", || 2);
+            42
+        });
+
+        assert_eq!(result, 42);
+        assert_eq!(
+            details,
+            vec![
+                Detail {
+                    key: "route".into(),
+                    value: "a -> b".into()
+                },
+                Detail {
+                    key: "length".into(),
+                    value: "2".into()
+                }
+            ]
+        );
+        assert!(!is_enabled());
+    }
+}
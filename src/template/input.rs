@@ -0,0 +1,348 @@
+use std::{
+    fmt::Display,
+    io::{self, Read},
+    ops::Deref,
+    path::PathBuf,
+};
+
+use grid_2d::{Grid, Size};
+
+use crate::parse::ParseError;
+use crate::template::{try_read_file, try_read_file_part, Day};
+
+/// Puzzle input passed to solutions by the `solution!` macro.
+///
+/// Line endings are normalised to `\n` and trailing whitespace is removed. Dereferences to `&str`,
+/// so solutions taking `&str` keep working.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input(String);
+
+impl Input {
+    pub fn new(raw: &str) -> Self {
+        Self(raw.replace("\r\n", "\n").trim_end().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.0.lines()
+    }
+
+    /// The input as a grid of characters, one row per line.
+    /// Lines shorter than the longest line are padded with spaces.
+    pub fn grid(&self) -> Grid<char> {
+        let rows: Vec<Vec<char>> = self.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        Grid::new_fn(Size::new(width as u32, rows.len() as u32), |coord| {
+            rows[coord.y as usize]
+                .get(coord.x as usize)
+                .copied()
+                .unwrap_or(' ')
+        })
+    }
+
+    /// Every integer in the input, in order.
+    ///
+    /// A `-` directly in front of digits is read as a sign, unless it follows a letter or digit. So `x=-3` contains
+    /// `-3`, while ranges like `2-4` contain `2` and `4`. Fails on the first integer that does not fit into an `i64`.
+    pub fn ints(&self) -> Result<Vec<i64>, ParseError> {
+        let mut ints = vec![];
+
+        for (line_index, line) in self.lines().enumerate() {
+            let mut start: Option<usize> = None;
+            let mut previous: [Option<char>; 2] = [None, None];
+
+            for (index, c) in line.char_indices().chain([(line.len(), ' ')]) {
+                if c.is_ascii_digit() {
+                    if start.is_none() {
+                        let is_sign = previous[1] == Some('-')
+                            && !previous[0].is_some_and(char::is_alphanumeric);
+                        start = Some(if is_sign { index - 1 } else { index });
+                    }
+                } else if let Some(start) = start.take() {
+                    let int = line[start..index].parse().map_err(|_| {
+                        ParseError::new(
+                            line_index + 1,
+                            line,
+                            &line[start..],
+                            "an integer that fits into an i64".into(),
+                        )
+                    })?;
+                    ints.push(int);
+                }
+                previous = [previous[1], Some(c)];
+            }
+        }
+
+        Ok(ints)
+    }
+
+    /// Blocks of lines separated by blank lines.
+    pub fn paragraphs(&self) -> impl Iterator<Item = &str> {
+        self.0.split("\n\n")
+    }
+
+    /// The input as a single line.
+    ///
+    /// # Panics
+    /// Panics if the input consists of more than one line.
+    pub fn single_line(&self) -> &str {
+        assert!(
+            !self.0.contains('\n'),
+            "expected a single line of input, found {} lines",
+            self.lines().count()
+        );
+        &self.0
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Input {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'a> From<&'a Input> for &'a str {
+    fn from(value: &'a Input) -> Self {
+        &value.0
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Path of the marker that `--generate` writes next to a puzzle input it created, see [`is_input_generated`].
+pub fn get_generated_marker_path(day: Day) -> PathBuf {
+    PathBuf::from("data")
+        .join("inputs")
+        .join(format!("{day}.generated"))
+}
+
+/// Whether the puzzle input of a day was created by its generator instead of downloaded.
+/// Answers and timings computed from generated inputs are never submitted or stored.
+pub fn is_input_generated(day: Day) -> bool {
+    get_generated_marker_path(day).exists()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where a solution reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Puzzle,
+    /// An arbitrary file, passed via `--input <path>`.
+    File(PathBuf),
+    /// Standard input, passed via `--stdin`.
+    Stdin,
+    /// An example in `data/examples`, passed via `--example [N]`.
+    /// With a number, reads the example for that part like `read_file_part()` does.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Parse the input source from the arguments passed to a solution binary.
+    /// Fails if `--input` is not followed by a path.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value_after = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|index| args.get(index + 1))
+        };
+
+        match value_after("--input") {
+            Some(Some(path)) if !path.starts_with("--") => return Ok(Self::File(path.into())),
+            Some(_) => return Err("missing value for --input, expected a path".into()),
+            None => {}
+        }
+
+        if args.iter().any(|x| x == "--stdin") {
+            Ok(Self::Stdin)
+        } else if let Some(part) = value_after("--example") {
+            Ok(Self::Example(part.and_then(|x| x.parse().ok())))
+        } else {
+            Ok(Self::Puzzle)
+        }
+    }
+
+    /// Arguments that select this input source when passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            Self::Stdin => vec!["--stdin".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(part)) => vec!["--example".into(), part.to_string()],
+        }
+    }
+
+    /// Read the input for a day from this source.
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self {
+            Self::Puzzle => try_read_file("inputs", day),
+            Self::File(path) => std::fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Self::Example(None) => try_read_file("examples", day),
+            Self::Example(Some(part)) => try_read_file_part("examples", day, *part),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Puzzle => write!(f, "puzzle input"),
+            Self::File(path) => write!(f, "file \"{}\"", path.display()),
+            Self::Stdin => write!(f, "stdin"),
+            Self::Example(None) => write!(f, "example"),
+            Self::Example(Some(part)) => write!(f, "example {part}"),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Input, InputSource};
+    use crate::parse::ParseError;
+    use grid_2d::Coord;
+
+    #[test]
+    fn normalises_input() {
+        let input = Input::new("a\r\nb  \r\n\r\n");
+        assert_eq!(input.as_str(), "a\nb");
+        assert_eq!(&*input, "a\nb");
+        assert_eq!(input.lines().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn splits_paragraphs() {
+        let input = Input::new("a\nb\n\nc\n");
+        assert_eq!(input.paragraphs().collect::<Vec<_>>(), vec!["a\nb", "c"]);
+    }
+
+    #[test]
+    fn extracts_ints() {
+        let input = Input::new("x=-12, y=3..40\n7-2 a-5 (-1)");
+        assert_eq!(input.ints(), Ok(vec![-12, 3, 40, 7, 2, 5, -1]));
+        assert_eq!(Input::new("-3").ints(), Ok(vec![-3]));
+        assert_eq!(Input::new("").ints(), Ok(vec![]));
+    }
+
+    #[test]
+    fn extracts_large_ints() {
+        let input = Input::new("9223372036854775807 -9223372036854775808");
+        assert_eq!(input.ints(), Ok(vec![i64::MAX, i64::MIN]));
+    }
+
+    #[test]
+    fn rejects_ints_out_of_range() {
+        let error = Input::new("1\nx=99999999999999999999, y=2").ints();
+        assert_eq!(
+            error,
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                text: "x=99999999999999999999, y=2".into(),
+                expected: "an integer that fits into an i64".into(),
+            })
+        );
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "line 2, column 3: expected an integer that fits into an i64, found \"99999999999999999999, y=2\""
+        );
+    }
+
+    #[test]
+    fn builds_grid() {
+        let grid = Input::new("ab\nc\n").grid();
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(Coord::new(1, 0)), Some(&'b'));
+        assert_eq!(grid.get(Coord::new(1, 1)), Some(&' '));
+    }
+
+    #[test]
+    fn reads_single_line() {
+        assert_eq!(Input::new("abc\n").single_line(), "abc");
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_multiple_lines() {
+        let _ = Input::new("abc\ndef").single_line();
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(
+            InputSource::from_args(&args("01 --submit 1")),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --input foo.txt")),
+            Ok(InputSource::File("foo.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --stdin")),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example")),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example 2 --time")),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --example --time")),
+            Ok(InputSource::Example(None))
+        );
+    }
+
+    #[test]
+    fn requires_a_path_for_input() {
+        assert!(InputSource::from_args(&args("01 --input")).is_err());
+        assert!(InputSource::from_args(&args("01 --input --time")).is_err());
+    }
+
+    #[test]
+    fn round_trips_through_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File("data/foo.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+        ] {
+            let mut args = vec!["01".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&args), Ok(source));
+        }
+    }
+}
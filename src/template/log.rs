@@ -0,0 +1,161 @@
+/// Leveled logging for solutions, see the [`debug!`](crate::debug) and [`trace!`](crate::trace) macros.
+///
+/// Logs never go to stdout, so they can't interfere with the `Part N:` lines the runner parses. They are written to
+/// stderr, or to `data/logs/<day>.log` with `--log`. The verbosity is selected with `-v` (debug) or `-vv` (trace).
+use std::env;
+use std::fmt::Arguments;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::template::Day;
+
+pub const LEVEL_DEBUG: u8 = 1;
+pub const LEVEL_TRACE: u8 = 2;
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static PART: AtomicU8 = AtomicU8::new(0);
+static LOG_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Path of the log file for a day.
+pub fn get_log_path(day: Day) -> PathBuf {
+    PathBuf::from("data")
+        .join("logs")
+        .join(format!("{day}.log"))
+}
+
+/// Parses the verbosity from the arguments passed to a solution binary.
+pub fn get_level(args: &[String]) -> u8 {
+    if args.iter().any(|x| x == "-vv") {
+        LEVEL_TRACE
+    } else if args.iter().any(|x| x == "-v") {
+        LEVEL_DEBUG
+    } else {
+        0
+    }
+}
+
+/// Sets up logging from the command-line arguments. Called by `solution!`.
+pub fn init(day: Day) -> io::Result<()> {
+    let args: Vec<String> = env::args().collect();
+    let level = get_level(&args);
+
+    if level > 0 && args.iter().any(|x| x == "--log") {
+        let path = get_log_path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let _ = LOG_FILE.set(Mutex::new(File::create(path)?));
+    }
+
+    LEVEL.store(level, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Whether messages of `level` are currently logged.
+pub fn is_enabled(level: u8) -> bool {
+    level <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Runs `func` with logging turned off, e.g. while benchmarking.
+pub fn suspended<T>(func: impl FnOnce() -> T) -> T {
+    let level = LEVEL.swap(0, Ordering::Relaxed);
+    let result = func();
+    LEVEL.store(level, Ordering::Relaxed);
+    result
+}
+
+/// Sets the part that following messages are attributed to.
+pub fn set_part(part: u8) {
+    PART.store(part, Ordering::Relaxed);
+}
+
+fn format_line(level: u8, part: u8, args: Arguments) -> String {
+    let label = if level >= LEVEL_TRACE {
+        "TRACE"
+    } else {
+        "DEBUG"
+    };
+
+    match part {
+        0 => format!("[{label}] {args}"),
+        part => format!("[{label} part {part}] {args}"),
+    }
+}
+
+/// Writes a message. Use the [`debug!`](crate::debug) and [`trace!`](crate::trace) macros instead of calling this.
+pub fn write(level: u8, args: Arguments) {
+    let line = format_line(level, PART.load(Ordering::Relaxed), args);
+
+    match LOG_FILE.get() {
+        Some(file) => {
+            if let Ok(mut file) = file.lock() {
+                let _ = writeln!(file, "{line}");
+            }
+        }
+        None => eprintln!("{line}"),
+    }
+}
+
+/// Logs a message when running with `-v` or `-vv`. Compiled out of optimized builds unless the `trace` feature is
+/// enabled, which `cargo solve --release -v` does.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::__log!($crate::template::log::LEVEL_DEBUG, $($arg)*)
+    };
+}
+
+/// Logs a message when running with `-vv`. Compiled out like [`debug!`](crate::debug).
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::__log!($crate::template::log::LEVEL_TRACE, $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:expr, $($arg:tt)*) => {{
+        #[cfg(any(debug_assertions, feature = "trace"))]
+        if $crate::template::log::is_enabled($level) {
+            $crate::template::log::write($level, format_args!($($arg)*));
+        }
+        // NOTE: keeps variables that are only used for logging from being reported as unused.
+        #[cfg(not(any(debug_assertions, feature = "trace")))]
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_line, get_level, LEVEL_DEBUG, LEVEL_TRACE};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_level() {
+        assert_eq!(get_level(&args("07 --time")), 0);
+        assert_eq!(get_level(&args("07 -v")), LEVEL_DEBUG);
+        assert_eq!(get_level(&args("07 --example -vv")), LEVEL_TRACE);
+    }
+
+    #[test]
+    fn formats_lines() {
+        assert_eq!(
+            format_line(LEVEL_DEBUG, 0, format_args!("x = {}", 1)),
+            "[DEBUG] x = 1"
+        );
+        assert_eq!(
+            format_line(LEVEL_TRACE, 2, format_args!("wire {}", "a")),
+            "[TRACE part 2] wire a"
+        );
+    }
+}
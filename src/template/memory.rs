@@ -0,0 +1,181 @@
+/// Counts the heap allocations of solution parts while they are benchmarked.
+///
+/// `solution!` installs [`CountingAlloc`] as the global allocator of every solution. It only counts while a part is
+/// measured with [`measure`], which the runner does for one extra, untimed run in benchmark mode. Otherwise, allocations go
+/// straight to the system allocator. Unlike the `dhat` profile, this is cheap enough to run with every `cargo time`.
+///
+/// As the counted run follows the first one, it misses allocations of global caches that are only filled once, unless
+/// the solution clears them with a `reset` function.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+/// Bytes allocated minus bytes freed since counting started. Negative if memory allocated before was freed.
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+static PEAK_BYTES: AtomicIsize = AtomicIsize::new(0);
+
+/// A global allocator that counts allocations, see the module docs.
+pub struct CountingAlloc;
+
+#[allow(clippy::cast_possible_wrap)]
+fn track(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if allocated > 0 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(allocated as u64, Ordering::Relaxed);
+    }
+
+    let change = allocated as isize - freed as isize;
+    let live = LIVE_BYTES.fetch_add(change, Ordering::Relaxed) + change;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            track(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            track(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        track(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            track(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// The heap usage of a single run of a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Sum of the sizes of all allocations.
+    pub allocated_bytes: u64,
+    /// The largest amount of memory that was allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+/// Runs `func` and counts its allocations. Returns `None` if the counting allocator is not installed, e.g. in the
+/// `dhat` profile, which brings its own allocator.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryUsage>) {
+    if cfg!(feature = "dhat-heap") {
+        return (func(), None);
+    }
+
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    ALLOCATED_BYTES.store(0, Ordering::Relaxed);
+    LIVE_BYTES.store(0, Ordering::Relaxed);
+    PEAK_BYTES.store(0, Ordering::Relaxed);
+
+    ENABLED.store(true, Ordering::Relaxed);
+    let result = func();
+    ENABLED.store(false, Ordering::Relaxed);
+
+    let usage = MemoryUsage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).max(0).unsigned_abs() as u64,
+    };
+
+    (result, Some(usage))
+}
+
+/* -------------------------------------------------------------------------- */
+
+const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`. Only for display, the result is rounded.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hint::black_box;
+
+    use super::{format_bytes, measure, CountingAlloc, MemoryUsage};
+
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn counts_allocations() {
+        let (_, usage) = measure(|| {
+            let mut v: Vec<u8> = black_box(Vec::with_capacity(4096));
+            v.push(1);
+            drop(v);
+            black_box(vec![0u8; 1024])
+        });
+        let usage = usage.unwrap();
+
+        // NOTE: tests run in parallel, other threads may allocate while counting.
+        assert!(usage.allocations >= 2);
+        assert!(usage.allocated_bytes >= 5120);
+        assert!(usage.peak_bytes >= 4096);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn formats_memory_usage() {
+        let usage = MemoryUsage {
+            allocations: 12,
+            allocated_bytes: 2048,
+            peak_bytes: 100,
+        };
+
+        assert_eq!(usage.to_string(), "12 allocs, 2.0 KiB, peak 100 B");
+    }
+}
@@ -1,12 +1,24 @@
-use std::{env, fs};
+use std::{env, fs, io, path::Path};
 
 pub mod aoc_cli;
+pub mod cachegrind;
 pub mod commands;
+pub mod explain;
+pub mod log;
+pub mod memory;
+pub mod params;
+pub mod property;
+pub mod puzzle;
 pub mod runner;
+pub mod scaling;
 
+pub use answer::*;
 pub use day::*;
+pub use input::*;
 
+mod answer;
 mod day;
+mod input;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
@@ -18,40 +30,268 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
+    try_read_file(folder, day).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can't be read.
+pub fn try_read_file(folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    fs::read_to_string(filepath)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+    try_read_file_part(folder, day, part).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to string, appending a part suffix, returning an error if it can't be read.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> io::Result<String> {
+    let cwd = env::current_dir()?;
     let filepath = cwd
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    fs::read_to_string(filepath)
+}
+
+/// How the source of a day's solution is laid out in `src/bin`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinLayout {
+    /// A single file, `src/bin/<day>.rs`.
+    File,
+    /// A directory with `src/bin/<day>/main.rs` and further modules next to it, for larger solutions.
+    Dir,
+}
+
+impl BinLayout {
+    /// Path of the file containing `main()` for this layout.
+    pub fn path(self, day: Day) -> String {
+        match self {
+            BinLayout::File => format!("src/bin/{day}.rs"),
+            BinLayout::Dir => format!("src/bin/{day}/main.rs"),
+        }
+    }
+
+    /// The layout of a day's solution, if one exists.
+    pub fn detect(day: Day) -> Option<Self> {
+        [BinLayout::Dir, BinLayout::File]
+            .into_iter()
+            .find(|layout| Path::new(&layout.path(day)).exists())
+    }
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Options can follow the day number, separated by commas:
+///  - `1` or `2` only runs a single part of the solution.
+///  - `params { name: Type = default, ... }` declares parameters that differ between inputs, e.g. the number of
+///    rounds to simulate. This generates a `Params` struct, which is passed to the parts as a second argument.
+///    Parameters can be overridden for an example with a sidecar file `data/examples/<day>.params`
+///    (`<day>-<N>.params` for `--example N`) and on the command-line with `--param <name>=<value>`.
+///    Tests get the example's parameters with `Params::example()` or `Params::example_part(N)`.
+///  - `owned = setup` passes the parts an owned value created by `setup(input)` instead of the input, so they can
+///    consume or mutate it. `owned(N) = setup` does the same for part `N` only. When benchmarking, `setup` runs
+///    before every iteration and is not included in the timings, so it should only copy data, e.g. clone the input.
+///  - `reset = function` calls `function()` before every run of a part, e.g. to clear global caches such as those
+///    of `#[memoize]`, so that benchmarks measure a full run. It is not included in the timings either.
+///  - `variants(N) { name = function, ... }` declares alternative implementations of part `N`, which take the same
+///    arguments as the part. They only run with `--variants`, which checks that they agree with the part and
+///    compares their timings.
+///  - `generate = function` declares a generator of synthetic inputs, see [`runner::Generator`]. It is used by
+///    `cargo generate-input`, so the solution can be run and benchmarked without a real input.
+///  - `scaling = sizes` sets the sizes of the generated inputs that `cargo time <day> --scaling` runs the parts on,
+///    e.g. `2..=9`. By default, sizes double from 16 until a part takes longer than a second.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, $($options:tt)*)?) => {
+        $crate::solution!(@parse [$day] [] [] [] [] [] [] [[] []] [] [] $($($options)*)?);
+    };
+
+    // State: [day] [parts] [params] [owned setup for all parts] [... for part 1] [... for part 2] [reset]
+    //        [[variants of part 1] [variants of part 2]] [input generator] [input sizes for --scaling]
+    (@parse [$day:expr] [$($parts:tt)*] $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt 1 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] [$($parts)* 1] $params $all $one $two $reset $variants $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] [$($parts:tt)*] $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt 2 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] [$($parts)* 2] $params $all $one $two $reset $variants $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt [] $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt params { $($params:tt)* } $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts [$($params)*] $all $one $two $reset $variants $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt [] $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt owned = $setup:path $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params [$setup] $one $two $reset $variants $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt [] $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt owned(1) = $setup:path $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all [$setup] $two $reset $variants $generate $sizes $($($rest)*)?);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt [] $reset:tt $variants:tt $generate:tt $sizes:tt owned(2) = $setup:path $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one [$setup] $reset $variants $generate $sizes $($($rest)*)?);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt $two:tt [] $variants:tt $generate:tt $sizes:tt reset = $reset:path $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one $two [$reset] $variants $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt $two:tt $reset:tt [[] $v2:tt] $generate:tt $sizes:tt variants(1) { $($name:ident = $func:path),+ $(,)? } $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one $two $reset [[$($name = $func),+] $v2] $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt $two:tt $reset:tt [$v1:tt []] $generate:tt $sizes:tt variants(2) { $($name:ident = $func:path),+ $(,)? } $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one $two $reset [$v1 [$($name = $func),+]] $generate $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt [] $sizes:tt generate = $generate:path $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one $two $reset $variants [$generate] $sizes $($($rest)*)?);
+    };
+    (@parse [$day:expr] $parts:tt $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt [] scaling = $sizes:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@parse [$day] $parts $params $all $one $two $reset $variants $generate [$sizes] $($($rest)*)?);
+    };
+    (@parse [$day:expr] [] $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt) => {
+        $crate::solution!(@impl [$day] [1 2] $params [$all $one $two] $reset $variants $generate $sizes);
+    };
+    (@parse [$day:expr] [$($parts:tt)+] $params:tt $all:tt $one:tt $two:tt $reset:tt $variants:tt $generate:tt $sizes:tt) => {
+        $crate::solution!(@impl [$day] [$($parts)+] $params [$all $one $two] $reset $variants $generate $sizes);
+    };
+
+    (@impl [$day:expr] [$($part:tt)+] [] $setups:tt $reset:tt $variants:tt [$($generate:path)?] [$($sizes:expr)?]) => {
+        $crate::solution!(@common $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let generator = $crate::solution!(@generator $($generate)?);
+            generate_input_if_requested(DAY, generator);
+            init_logging_or_exit(DAY);
+            let run = |input: &$crate::template::Input| {
+                $( $crate::solution!(@run $part $setups $reset $variants input []); )+
+            };
+            $crate::template::scaling::run_if_requested(DAY, generator, $crate::solution!(@sizes $($sizes)?), run);
+            run(&read_input_or_exit(DAY));
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl [$day:expr] [$($part:tt)+] [$($name:ident : $ty:ty = $default:expr),+ $(,)?] $setups:tt $reset:tt $variants:tt [$($generate:path)?] [$($sizes:expr)?]) => {
+        $crate::solution!(@common $day);
+
+        /// Parameters of the solution that differ between inputs.
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct Params {
+            $( pub $name: $ty, )+
+        }
+
+        impl Default for Params {
+            fn default() -> Self {
+                Self {
+                    $( $name: $crate::template::params::ParamDefault::into_param($default), )+
+                }
+            }
+        }
+
+        impl $crate::template::params::SolutionParams for Params {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(
+                        stringify!($name) => {
+                            self.$name = value.parse().map_err(|e| {
+                                format!("invalid value {value:?} for parameter `{key}`: {e}")
+                            })?;
+                        }
+                    )+
+                    _ => return Err(format!("unknown parameter `{key}`")),
+                }
+                Ok(())
+            }
+        }
+
+        #[allow(dead_code)]
+        impl Params {
+            /// Parameters for `data/examples/<day>.txt`.
+            pub fn example() -> Self {
+                $crate::template::params::for_example(DAY, None).unwrap()
+            }
+
+            /// Parameters for `data/examples/<day>-<part>.txt`.
+            pub fn example_part(part: u8) -> Self {
+                $crate::template::params::for_example(DAY, Some(part)).unwrap()
+            }
+        }
+
+        fn main() {
+            use $crate::template::runner::*;
+            let generator = $crate::solution!(@generator $($generate)?);
+            generate_input_if_requested(DAY, generator);
+            init_logging_or_exit(DAY);
+            let params: Params = read_params_or_exit(DAY);
+            let run = |input: &$crate::template::Input| {
+                $( $crate::solution!(@run $part $setups $reset $variants input [params]); )+
+            };
+            $crate::template::scaling::run_if_requested(DAY, generator, $crate::solution!(@sizes $($sizes)?), run);
+            run(&read_input_or_exit(DAY));
+        }
+    };
+
+    // Picks the setup for a part: a part-specific one takes precedence over one for all parts.
+    (@run 1 [$all:tt [$setup:path] $two:tt] $reset:tt [$v1:tt $v2:tt] $($args:tt)*) => {
+        $crate::solution!(@call part_one 1 [$setup] $reset $v1 $($args)*)
+    };
+    (@run 1 [[$($setup:path)?] [] $two:tt] $reset:tt [$v1:tt $v2:tt] $($args:tt)*) => {
+        $crate::solution!(@call part_one 1 [$($setup)?] $reset $v1 $($args)*)
+    };
+    (@run 2 [$all:tt $one:tt [$setup:path]] $reset:tt [$v1:tt $v2:tt] $($args:tt)*) => {
+        $crate::solution!(@call part_two 2 [$setup] $reset $v2 $($args)*)
+    };
+    (@run 2 [[$($setup:path)?] $one:tt []] $reset:tt [$v1:tt $v2:tt] $($args:tt)*) => {
+        $crate::solution!(@call part_two 2 [$($setup)?] $reset $v2 $($args)*)
+    };
+
+    (@call $func:ident $part:literal [] [] [] $input:ident [$($params:ident)?]) => {
+        run_part(|input| $func(input $(, &$params)?), &$input, DAY, $part)
+    };
+    (@call $func:ident $part:literal [$($setup:path)?] [$($reset:path)?] [] $input:ident [$($params:ident)?]) => {
+        run_part_batched(
+            |input| {
+                $( $reset(); )?
+                $( let input = $setup(input); )?
+                input
+            },
+            |data| $func(data $(, &$params)?),
+            &$input,
+            DAY,
+            $part,
+        )
+    };
+    (@call $func:ident $part:literal [$($setup:path)?] [$($reset:path)?] [$($name:ident = $variant:path),+] $input:ident $params:tt) => {
+        run_part_variants(
+            |input| {
+                $( $reset(); )?
+                $( let input = $setup(input); )?
+                input
+            },
+            vec![
+                Variant::new(stringify!($func), |data| $crate::solution!(@apply $func, data, $params)),
+                $( Variant::new(stringify!($name), |data| $crate::solution!(@apply $variant, data, $params)), )+
+            ],
+            &$input,
+            DAY,
+            $part,
+        )
+    };
+    (@apply $func:path, $data:ident, [$($params:ident)?]) => {
+        $func($data $(, &$params)?)
+    };
+
+    (@generator) => {
+        None
+    };
+    (@generator $generate:path) => {
+        Some($generate as Generator)
+    };
+
+    (@sizes) => {
+        None
+    };
+    (@sizes $sizes:expr) => {
+        Some(IntoIterator::into_iter($sizes).collect())
+    };
+
+    (@common $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +299,8 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        #[cfg(not(feature = "dhat-heap"))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
     };
 }
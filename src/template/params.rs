@@ -0,0 +1,153 @@
+/// Support for per-input solution parameters declared with `solution!(DAY, params { ... })`.
+///
+/// Parameters start out with the defaults from the declaration. When running against an example,
+/// they are overridden by the example's sidecar file (`data/examples/<day>.params` or `<day>-<N>.params`),
+/// and finally by `--param <key>=<value>` arguments.
+use std::{env, fs, io, path::PathBuf};
+
+use crate::template::{Day, InputSource};
+
+/// Implemented by the `Params` struct that `solution!` generates.
+pub trait SolutionParams: Default {
+    /// Overrides a single parameter, parsing `value` into the parameter's type.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Overrides parameters from `key=value` or `key = value` assignments.
+    fn apply<'a>(&mut self, assignments: impl IntoIterator<Item = &'a str>) -> Result<(), String> {
+        for assignment in assignments {
+            let (key, value) = parse_assignment(assignment)?;
+            self.set(key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts the default value of a parameter declaration into the parameter's type.
+/// Unlike `Into`, this lets integer literals infer their type from the declaration, while still allowing
+/// `&str` literals as defaults for `String` parameters.
+pub trait ParamDefault<T> {
+    fn into_param(self) -> T;
+}
+
+impl<T> ParamDefault<T> for T {
+    fn into_param(self) -> T {
+        self
+    }
+}
+
+impl ParamDefault<String> for &str {
+    fn into_param(self) -> String {
+        self.to_string()
+    }
+}
+
+fn parse_assignment(s: &str) -> Result<(&str, &str), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or_else(|| format!("expected `<key>=<value>`, found {s:?}"))
+}
+
+/// Path of the sidecar file holding parameter overrides for an example.
+pub fn get_sidecar_path(day: Day, part: Option<u8>) -> PathBuf {
+    let file_name = match part {
+        Some(part) => format!("{day}-{part}.params"),
+        None => format!("{day}.params"),
+    };
+    PathBuf::from("data").join("examples").join(file_name)
+}
+
+/// Reads sidecar assignments, one per line. Blank lines and lines starting with `#` are skipped.
+/// A missing sidecar is treated as empty.
+fn read_sidecar(day: Day, part: Option<u8>) -> Result<Vec<String>, String> {
+    let path = get_sidecar_path(day, part);
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+    }
+}
+
+/// Parameters for an example input: the defaults, overridden by the example's sidecar file.
+pub fn for_example<P: SolutionParams>(day: Day, part: Option<u8>) -> Result<P, String> {
+    let mut params = P::default();
+    params.apply(read_sidecar(day, part)?.iter().map(String::as_str))?;
+    Ok(params)
+}
+
+/// Values of all `--param <key>=<value>` arguments.
+pub fn get_param_args(args: &[String]) -> Vec<&str> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--param")
+        .map(|pair| pair[1].as_str())
+        .collect()
+}
+
+/// Parameters for the current invocation of a solution binary.
+pub fn from_env<P: SolutionParams>(day: Day) -> Result<P, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let mut params = match InputSource::from_args(&args)? {
+        InputSource::Example(part) => for_example(day, part)?,
+        _ => P::default(),
+    };
+
+    params.apply(get_param_args(&args))?;
+    Ok(params)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{get_param_args, parse_assignment, SolutionParams};
+
+    #[derive(Default)]
+    struct TestParams {
+        rounds: u32,
+        name: String,
+    }
+
+    impl SolutionParams for TestParams {
+        fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+            match key {
+                "rounds" => self.rounds = value.parse().map_err(|_| "bad rounds".to_string())?,
+                "name" => self.name = value.into(),
+                _ => return Err(format!("unknown parameter `{key}`")),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(parse_assignment("rounds=5"), Ok(("rounds", "5")));
+        assert_eq!(parse_assignment(" name = a b "), Ok(("name", "a b")));
+        assert_eq!(parse_assignment("a=b=c"), Ok(("a", "b=c")));
+        assert!(parse_assignment("rounds").is_err());
+        assert!(parse_assignment("=5").is_err());
+    }
+
+    #[test]
+    fn applies_assignments() {
+        let mut params = TestParams::default();
+        params.apply(["rounds=5", "name = x"]).unwrap();
+        assert_eq!(params.rounds, 5);
+        assert_eq!(params.name, "x");
+        assert!(params.apply(["foo=1"]).is_err());
+        assert!(params.apply(["rounds=x"]).is_err());
+    }
+
+    #[test]
+    fn collects_param_args() {
+        let args: Vec<String> = ["07", "--param", "wire=d", "--time", "--param", "rounds=1"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(get_param_args(&args), vec!["wire=d", "rounds=1"]);
+    }
+}
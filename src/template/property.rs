@@ -0,0 +1,331 @@
+/// A small property-based testing harness for solutions.
+///
+/// A property pairs a generator of inputs with an invariant that has to hold for all of them, e.g. that the floor of
+/// day 1 equals the number of `(` minus the number of `)`. Runs are seeded from the day and the name of the property,
+/// so they are reproducible. When a property fails, its input is shrunk to a minimal one before reporting it.
+///
+/// Properties of a whole solution take their inputs from the generator declared with `generate =` in `solution!`,
+/// see [`from_generator`], and check the answers of its parts with [`Property::check_parts`].
+use std::env;
+use std::fmt::Debug;
+
+use crate::template::{runner::Generator, Day};
+
+/// Overrides the seed of all properties, e.g. to reproduce a failure reported with a different seed.
+const SEED_VAR: &str = "AOC_PROPERTY_SEED";
+
+const DEFAULT_CASES: usize = 256;
+
+/// The size passed to generators grows from `0` to this over the cases of a run.
+pub const MAX_SIZE: usize = 100;
+
+/// Gives up shrinking after this many steps, in case a shrinker keeps producing failing inputs.
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// A SplitMix64 pseudo-random number generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. `n` must not be zero.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A random element of a non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+type GenerateFn<T> = Box<dyn Fn(&mut Rng, usize) -> T>;
+type ShrinkFn<T> = Box<dyn Fn(&T) -> Vec<T>>;
+
+/// Generates inputs for a property and shrinks failing ones.
+pub struct Gen<T> {
+    generate: GenerateFn<T>,
+    shrink: ShrinkFn<T>,
+}
+
+impl<T> Gen<T> {
+    /// `generate` creates an input of roughly the given size, between `0` and [`MAX_SIZE`].
+    /// `shrink` lists simpler variants of an input, simplest first.
+    pub fn new(
+        generate: impl Fn(&mut Rng, usize) -> T + 'static,
+        shrink: impl Fn(&T) -> Vec<T> + 'static,
+    ) -> Self {
+        Self {
+            generate: Box::new(generate),
+            shrink: Box::new(shrink),
+        }
+    }
+
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> T {
+        (self.generate)(rng, size)
+    }
+
+    pub fn shrink(&self, value: &T) -> Vec<T> {
+        (self.shrink)(value)
+    }
+}
+
+/// The length of a collection for a given size, up to `max_len`.
+pub fn length(rng: &mut Rng, size: usize, max_len: usize) -> usize {
+    rng.below(max_len * size / MAX_SIZE + 1)
+}
+
+/// Simpler variants of a sequence: the empty one, followed by the sequence with chunks of decreasing length removed.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    if items.is_empty() {
+        return vec![];
+    }
+
+    let mut candidates = vec![vec![]];
+    let mut chunk = items.len() / 2;
+
+    while chunk > 0 {
+        for start in (0..=items.len() - chunk).step_by(chunk) {
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[start + chunk..]);
+            candidates.push(candidate);
+        }
+        chunk /= 2;
+    }
+
+    candidates
+}
+
+/// Strings of up to `max_len` characters from `alphabet`.
+pub fn string_of(alphabet: &str, max_len: usize) -> Gen<String> {
+    let chars: Vec<char> = alphabet.chars().collect();
+
+    Gen::new(
+        move |rng, size| {
+            let len = length(rng, size, max_len);
+            (0..len).map(|_| *rng.pick(&chars)).collect()
+        },
+        |s: &String| {
+            let chars: Vec<char> = s.chars().collect();
+            shrink_vec(&chars)
+                .into_iter()
+                .map(|candidate| candidate.into_iter().collect())
+                .collect()
+        },
+    )
+}
+
+/// Inputs from a day's generator, see the `generate` option of `solution!`. As the meaning of a size is up to the day,
+/// the size of each case is scaled to `0..max_size` before it is passed on. Failing inputs are shrunk by removing
+/// lines, or characters if the input is a single line.
+pub fn from_generator(generator: Generator, max_size: usize) -> Gen<String> {
+    Gen::new(
+        move |rng, size| generator(rng, Some(max_size * size / MAX_SIZE)),
+        |input: &String| {
+            let lines: Vec<&str> = input.lines().collect();
+            if lines.len() > 1 {
+                shrink_vec(&lines)
+                    .into_iter()
+                    .map(|candidate| candidate.join("\n"))
+                    .collect()
+            } else {
+                let chars: Vec<char> = input.chars().collect();
+                shrink_vec(&chars)
+                    .into_iter()
+                    .map(|candidate| candidate.into_iter().collect())
+                    .collect()
+            }
+        },
+    )
+}
+
+/// A property of a day's solution, see the module docs.
+pub struct Property {
+    name: String,
+    seed: u64,
+    cases: usize,
+}
+
+impl Property {
+    pub fn new(day: Day, name: &str) -> Self {
+        let seed = env::var(SEED_VAR)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(|| fnv1a(format!("{day}/{name}").as_bytes()));
+
+        Self {
+            name: format!("{name} (day {day})"),
+            seed,
+            cases: DEFAULT_CASES,
+        }
+    }
+
+    /// Sets the number of inputs to check.
+    #[must_use]
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Checks that `holds` is true for all generated inputs. Panics with a minimal failing input otherwise.
+    pub fn check<T: Debug>(&self, gen: &Gen<T>, holds: impl Fn(&T) -> bool) {
+        if let Err(failure) = self.run(gen, holds) {
+            panic!("{failure}");
+        }
+    }
+
+    /// Checks that `holds` is true for the answers of both parts on all generated inputs. The parts are usually the
+    /// day's `part_one` and `part_two`, wrapped in closures that pass them the input type and `Params` they expect.
+    pub fn check_parts<A, B>(
+        &self,
+        gen: &Gen<String>,
+        part_one: impl Fn(&str) -> A,
+        part_two: impl Fn(&str) -> B,
+        holds: impl Fn(&str, A, B) -> bool,
+    ) {
+        self.check(gen, |input| holds(input, part_one(input), part_two(input)));
+    }
+
+    fn run<T: Debug>(&self, gen: &Gen<T>, holds: impl Fn(&T) -> bool) -> Result<(), String> {
+        let mut rng = Rng::new(self.seed);
+
+        for case in 0..self.cases {
+            let size = case * MAX_SIZE / self.cases.max(1);
+            let input = gen.generate(&mut rng, size);
+
+            if !holds(&input) {
+                let (minimal, steps) = shrink(gen, input, &holds);
+                return Err(format!(
+                    "property `{}` failed after {} cases\n  minimal input: {minimal:?} (shrunk {steps} times)\n  \
+                    reproduce with {SEED_VAR}={}",
+                    self.name,
+                    case + 1,
+                    self.seed
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Repeatedly replaces a failing input with the first simpler variant that still fails.
+fn shrink<T>(gen: &Gen<T>, mut input: T, holds: impl Fn(&T) -> bool) -> (T, usize) {
+    let mut steps = 0;
+
+    while steps < MAX_SHRINK_STEPS {
+        match gen.shrink(&input).into_iter().find(|c| !holds(c)) {
+            Some(simpler) => {
+                input = simpler;
+                steps += 1;
+            }
+            None => break,
+        }
+    }
+
+    (input, steps)
+}
+
+/// FNV-1a, a simple hash that is stable across platforms and releases, unlike the std hashers.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_generator, shrink_vec, string_of, Property, Rng};
+    use crate::day;
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        assert_eq!(
+            (0..5).map(|_| a.next_u64()).collect::<Vec<_>>(),
+            (0..5).map(|_| b.next_u64()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn shrinks_sequences() {
+        assert_eq!(shrink_vec::<u8>(&[]), Vec::<Vec<u8>>::new());
+        assert_eq!(
+            shrink_vec(&[1, 2, 3, 4]),
+            vec![
+                vec![],
+                vec![3, 4],
+                vec![1, 2],
+                vec![2, 3, 4],
+                vec![1, 3, 4],
+                vec![1, 2, 4],
+                vec![1, 2, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn passes_holding_properties() {
+        let property = Property::new(day!(1), "length");
+        assert!(property
+            .run(&string_of("ab", 50), |s: &String| s.len() <= 50)
+            .is_ok());
+    }
+
+    #[test]
+    fn reports_minimal_failing_input() {
+        let property = Property::new(day!(1), "no_b");
+        let failure = property
+            .run(&string_of("ab", 50), |s: &String| !s.contains('b'))
+            .unwrap_err();
+
+        assert!(failure.starts_with("property `no_b (day 01)` failed"));
+        assert!(failure.contains("minimal input: \"b\""));
+    }
+
+    #[test]
+    fn generates_from_solution_generators() {
+        let numbers = |_: &mut Rng, size: Option<usize>| -> String {
+            (0..size.unwrap_or(100))
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let gen = from_generator(numbers, 5);
+
+        let property = Property::new(day!(1), "sized");
+        assert!(property
+            .run(&gen, |input: &String| input.lines().count() <= 5)
+            .is_ok());
+
+        let failure = property
+            .run(&gen, |input: &String| !input.contains('3'))
+            .unwrap_err();
+        assert!(failure.contains("minimal input: \"3\""));
+
+        let digits = |_: &mut Rng, size: Option<usize>| -> String {
+            (0..size.unwrap_or(10)).map(|n| n.to_string()).collect()
+        };
+        let failure = property
+            .run(&from_generator(digits, 10), |input: &String| {
+                !input.contains('3')
+            })
+            .unwrap_err();
+        assert!(failure.contains("minimal input: \"3\""));
+    }
+}
@@ -0,0 +1,280 @@
+/// Reads puzzle descriptions downloaded by aoc-cli, and keeps the module docs of solutions in sync with them.
+use std::fs;
+
+use crate::template::{aoc_cli, Day};
+
+/// The parts of a puzzle description that end up in the docs of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The title from the `--- Day N: Title ---` heading.
+    pub title: String,
+    /// The first paragraph of part one.
+    pub part_one: String,
+    /// The first paragraph of part two, once it has been unlocked.
+    pub part_two: Option<String>,
+}
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+impl Puzzle {
+    /// Parses the markdown written by `aoc read`.
+    pub fn parse(markdown: &str) -> Option<Self> {
+        let (part_one, part_two) = match markdown.split_once(PART_TWO_HEADING) {
+            Some((part_one, part_two)) => (part_one, Some(part_two)),
+            None => (markdown, None),
+        };
+
+        let title = part_one
+            .lines()
+            .find_map(|line| line.split_once("--- Day ")?.1.split_once(": "))
+            .map(|(_, title)| title.trim_end_matches(['-', ' ']).to_string())?;
+
+        Some(Self {
+            title,
+            part_one: first_paragraph(part_one)?,
+            part_two: part_two.and_then(first_paragraph),
+        })
+    }
+}
+
+/// The first paragraph following a section heading, joined into a single line.
+fn first_paragraph(section: &str) -> Option<String> {
+    let paragraph: Vec<&str> = section
+        .lines()
+        .skip_while(|line| !line.contains("---"))
+        .skip(1)
+        .map(str::trim)
+        .skip_while(|line| line.is_empty() || line.chars().all(|c| c == '-'))
+        .take_while(|line| !line.is_empty())
+        .collect();
+
+    (!paragraph.is_empty()).then(|| paragraph.join(" "))
+}
+
+/// The downloaded puzzle for a day, if any.
+pub fn read(day: Day) -> Option<Puzzle> {
+    Puzzle::parse(&fs::read_to_string(aoc_cli::get_puzzle_path(day)).ok()?)
+}
+
+/// The heading of a solution's docs, e.g. `Day 9: All in a Single Night`, or `Day 9` without a title.
+pub fn heading(day: Day, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("Day {}: {title}", day.into_inner()),
+        None => format!("Day {}", day.into_inner()),
+    }
+}
+
+/// The link to the puzzle on the Advent of Code website.
+pub fn get_url(day: Day) -> String {
+    match aoc_cli::get_year() {
+        Some(year) => format!("https://adventofcode.com/{year}/day/{}", day.into_inner()),
+        None => "https://adventofcode.com".to_string(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const DOC_PREFIX: &str = "//!";
+const DOC_WIDTH: usize = 100;
+
+/// Wraps text into `//!` lines.
+fn wrap_docs(text: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = DOC_PREFIX.to_string();
+
+    for word in text.split_whitespace() {
+        if line.len() > DOC_PREFIX.len() && line.len() + 1 + word.len() > DOC_WIDTH {
+            lines.push(line);
+            line = DOC_PREFIX.to_string();
+        }
+        line.push(' ');
+        line.push_str(word);
+    }
+
+    lines.push(line);
+    lines
+}
+
+/// Module docs for a solution, summarizing the puzzle.
+pub fn render_docs(day: Day, puzzle: &Puzzle, url: &str) -> String {
+    let mut lines = vec![
+        format!("{DOC_PREFIX} # {}", heading(day, Some(&puzzle.title))),
+        DOC_PREFIX.to_string(),
+    ];
+
+    lines.extend(wrap_docs(&puzzle.part_one));
+
+    if let Some(part_two) = &puzzle.part_two {
+        lines.push(DOC_PREFIX.to_string());
+        lines.push(format!("{DOC_PREFIX} ## Part Two"));
+        lines.push(DOC_PREFIX.to_string());
+        lines.extend(wrap_docs(part_two));
+    }
+
+    lines.push(DOC_PREFIX.to_string());
+    lines.push(format!("{DOC_PREFIX} <{url}>"));
+    lines.join("\n")
+}
+
+/// Replaces the module docs at the top of a solution's source, or adds them if there are none.
+pub fn replace_docs(source: &str, docs: &str) -> String {
+    let code: Vec<&str> = source
+        .lines()
+        .skip_while(|line| line.starts_with(DOC_PREFIX))
+        .skip_while(|line| line.trim().is_empty())
+        .collect();
+
+    let mut result = format!("{docs}\n\n{}", code.join("\n"));
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Whether the module docs at the top of a solution's source can be refreshed without losing anything written by hand:
+/// there are none, they are still the placeholder of a template, or they were rendered from the puzzle before.
+pub fn has_generated_docs(source: &str, day: Day, puzzle: &Puzzle, url: &str) -> bool {
+    let docs: Vec<&str> = source
+        .lines()
+        .take_while(|line| line.starts_with(DOC_PREFIX))
+        .collect();
+
+    let is_placeholder = matches!(
+        docs.as_slice(),
+        [heading, DOC_PREFIX, link]
+            if heading.starts_with(&format!("{DOC_PREFIX} # Day {}", day.into_inner()))
+                && link.starts_with(&format!("{DOC_PREFIX} <")),
+    );
+
+    // docs rendered before part two was unlocked are refreshed to add its summary.
+    let without_part_two = Puzzle {
+        part_two: None,
+        ..puzzle.clone()
+    };
+    let docs = docs.join("\n");
+
+    docs.is_empty()
+        || is_placeholder
+        || docs == render_docs(day, puzzle, url)
+        || docs == render_docs(day, &without_part_two, url)
+}
+
+/// The heading of a solution's module docs, e.g. `Day 9: All in a Single Night`.
+pub fn read_heading_from_docs(source: &str) -> Option<String> {
+    source
+        .lines()
+        .take_while(|line| line.starts_with(DOC_PREFIX))
+        .find_map(|line| line.strip_prefix("//! # "))
+        .map(|heading| heading.trim().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{has_generated_docs, read_heading_from_docs, render_docs, replace_docs, Puzzle};
+    use crate::day;
+
+    const PART_ONE: &str = "\\--- Day 9: All in a Single Night ---\n----------\n\nEvery year, Santa manages to deliver all of his presents in a single night.\n\nThis year, however, he has some new locations to visit.\n";
+    const PART_TWO: &str = "\n\\--- Part Two ---\n----------\n\nThe next year, just to show off, Santa decides to take the route with the *longest distance* instead.\n";
+
+    #[test]
+    fn parses_puzzles() {
+        assert_eq!(
+            Puzzle::parse(PART_ONE),
+            Some(Puzzle {
+                title: "All in a Single Night".into(),
+                part_one:
+                    "Every year, Santa manages to deliver all of his presents in a single night."
+                        .into(),
+                part_two: None,
+            })
+        );
+
+        let puzzle = Puzzle::parse(&format!("{PART_ONE}{PART_TWO}")).unwrap();
+        assert_eq!(
+            puzzle.part_two.as_deref(),
+            Some("The next year, just to show off, Santa decides to take the route with the *longest distance* instead.")
+        );
+
+        assert_eq!(Puzzle::parse("no heading"), None);
+    }
+
+    #[test]
+    fn renders_docs() {
+        let puzzle = Puzzle::parse(&format!("{PART_ONE}{PART_TWO}")).unwrap();
+        let docs = render_docs(day!(9), &puzzle, "https://adventofcode.com/2015/day/9");
+
+        assert_eq!(
+            docs,
+            [
+                "//! # Day 9: All in a Single Night",
+                "//!",
+                "//! Every year, Santa manages to deliver all of his presents in a single night.",
+                "//!",
+                "//! ## Part Two",
+                "//!",
+                "//! The next year, just to show off, Santa decides to take the route with the *longest distance*",
+                "//! instead.",
+                "//!",
+                "//! <https://adventofcode.com/2015/day/9>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn replaces_docs() {
+        let source =
+            "//! # Day 9\n//!\n//! <https://adventofcode.com>\n\nadvent_of_code::solution!(9);\n";
+        let replaced = replace_docs(source, "//! # Day 9: Title");
+        assert_eq!(
+            replaced,
+            "//! # Day 9: Title\n\nadvent_of_code::solution!(9);\n"
+        );
+        assert_eq!(replace_docs(&replaced, "//! # Day 9: Title"), replaced);
+
+        let without_docs = "use std::cmp;\n";
+        assert_eq!(
+            replace_docs(without_docs, "//! # Day 9: Title"),
+            "//! # Day 9: Title\n\nuse std::cmp;\n"
+        );
+    }
+
+    #[test]
+    fn detects_generated_docs() {
+        let url = "https://adventofcode.com/2015/day/9";
+        let part_one = Puzzle::parse(PART_ONE).unwrap();
+        let puzzle = Puzzle::parse(&format!("{PART_ONE}{PART_TWO}")).unwrap();
+        let is_generated = |docs: &str| {
+            has_generated_docs(
+                &format!("{docs}\n\nadvent_of_code::solution!(9);\n"),
+                day!(9),
+                &puzzle,
+                url,
+            )
+        };
+
+        assert!(is_generated(""));
+        assert!(is_generated(
+            "//! # Day 9\n//!\n//! <https://adventofcode.com>"
+        ));
+        assert!(is_generated(&render_docs(day!(9), &part_one, url)));
+        assert!(is_generated(&render_docs(day!(9), &puzzle, url)));
+
+        assert!(!is_generated(
+            "//! # Day 9: All in a Single Night\n//!\n//! Solved with Held-Karp."
+        ));
+        assert!(!is_generated(&format!(
+            "{}\n//! Notes of my own.",
+            render_docs(day!(9), &part_one, url)
+        )));
+    }
+
+    #[test]
+    fn reads_heading_from_docs() {
+        assert_eq!(
+            read_heading_from_docs("//! # Day 9: Title\n//!\n\nfn main() {}"),
+            Some("Day 9: Title".into())
+        );
+        assert_eq!(read_heading_from_docs("fn main() {}\n//! # Day 9"), None);
+    }
+}
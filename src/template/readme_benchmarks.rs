@@ -1,12 +1,16 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::memory::MemoryUsage;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{puzzle, BinLayout, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Set to `true` to add the memory usage of each part to the table.
+static MEMORY_COLUMNS_VAR: &str = "AOC_MEMORY_COLUMNS";
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,9 +29,11 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// Path of a day's solution. Solutions that don't exist yet are assumed to be a single file.
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    let layout = BinLayout::detect(day).unwrap_or(BinLayout::File);
+    format!("./{}", layout.path(day))
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,26 +58,63 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Builds the table. `get_heading` looks up the heading from the docs of a day's solution, if any.
+/// With `memory_columns`, the memory usage of the parts is added after their timings.
+/// Days without wall-clock timings, e.g. those only measured with `cargo time --instructions`, are left out.
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    get_heading: impl Fn(Day) -> Option<String>,
+    memory_columns: bool,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let (columns, alignment) = if memory_columns {
+        (
+            "| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |",
+            "| :---: | :---: | :---:  | :---: | :---: |",
+        )
+    } else {
+        ("| Day | Part 1 | Part 2 |", "| :---: | :---: | :---:  |")
+    };
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        columns.into(),
+        alignment.into(),
     ];
 
-    for timing in timings.data {
+    for timing in timings.data.into_iter().filter(Timing::is_timed) {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+        let cell = |part: u8, value: Option<String>| {
+            if timing.is_failed(part) {
+                "error".into()
+            } else {
+                value.unwrap_or_else(|| "-".into())
+            }
+        };
+        let heading = get_heading(timing.day).unwrap_or_else(|| puzzle::heading(timing.day, None));
+        let mut row = format!(
+            "| [{}]({}) | `{}` | `{}` |",
+            heading,
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            cell(1, timing.part_1.clone()),
+            cell(2, timing.part_2.clone())
+        );
+        if memory_columns {
+            let memory = |part: u8, value: Option<MemoryUsage>| {
+                cell(part, value.map(|memory| memory.to_string()))
+            };
+            row.push_str(&format!(
+                " `{}` | `{}` |",
+                memory(1, timing.memory_1),
+                memory(2, timing.memory_2)
+            ));
+        }
+        lines.push(row);
     }
 
     lines.push(String::new());
@@ -81,9 +124,15 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    get_heading: impl Fn(Day) -> Option<String>,
+    memory_columns: bool,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, get_heading, memory_columns);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,7 +141,17 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let memory_columns = env::var(MEMORY_COLUMNS_VAR).is_ok_and(|x| x == "true");
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        |day| {
+            let source = fs::read_to_string(get_path_for_bin(day)).ok()?;
+            puzzle::read_heading_from_docs(&source)
+        },
+        memory_columns,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,7 +159,12 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::template::{cachegrind::InstructionCounts, memory::MemoryUsage};
+    use crate::{day, template::timings::Timing, template::timings::Timings, template::Day};
+
+    fn get_mock_heading(day: Day) -> Option<String> {
+        (day == day!(2)).then(|| "Day 2: I Was Told There Would Be No Math".into())
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +174,28 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
+                },
+                Timing {
+                    day: day!(5),
+                    part_1: Some("60ms".into()),
+                    total_nanos: 6e+10,
+                    failed_parts: vec![2],
+                    ..Default::default()
                 },
             ],
         }
@@ -131,28 +205,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +234,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, get_mock_heading, false).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -170,8 +244,9 @@ mod tests {
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "| [Day 2: I Was Told There Would Be No Math](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 5](./src/bin/05.rs) | `60ms` | `error` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +255,40 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn adds_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory_1 = Some(MemoryUsage {
+            allocations: 3,
+            allocated_bytes: 1536,
+            peak_bytes: 512,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, get_mock_heading, true).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `3 allocs, 1.5 KiB, peak 512 B` | `-` |"
+        ));
+        assert!(s.contains("| [Day 5](./src/bin/05.rs) | `60ms` | `error` | `-` | `error` |"));
+    }
+
+    #[test]
+    fn skips_days_without_timings() {
+        let mut timings = get_mock_timings();
+        timings.data.push(Timing {
+            day: day!(6),
+            instructions_1: Some(InstructionCounts::default()),
+            instructions_2: Some(InstructionCounts::default()),
+            ..Default::default()
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, get_mock_heading, false).unwrap();
+
+        assert!(s.contains("[Day 5]"));
+        assert!(!s.contains("[Day 6]"));
+    }
 }
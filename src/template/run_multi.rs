@@ -1,6 +1,9 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    runner::{INPUT_MISSING, MEMORY_REPORT},
+    BinLayout, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
@@ -9,6 +12,8 @@ use super::{
 
 pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut missing_inputs: Vec<Day> = vec![];
+    let mut failed: Vec<Day> = vec![];
 
     let mut need_space = false;
 
@@ -24,16 +29,51 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let (output, success) =
+                child_commands::run_solution(day, is_timed, is_release).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
+            } else if child_commands::is_input_missing(&output) {
+                missing_inputs.push(day);
+            } else if !success {
+                // e.g. a panic or disagreeing variants, the timings of a failed run are not recorded.
+                failed.push(day);
             } else {
                 let val = child_commands::parse_exec_time(&output, day);
                 timings.push(val);
             }
         });
 
+    if !missing_inputs.is_empty() {
+        let days = missing_inputs
+            .iter()
+            .map(Day::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!(
+            "\n{ANSI_BOLD}No input:{ANSI_RESET} {} day(s) ({days}), run `cargo download <day>` to fetch them.",
+            missing_inputs.len()
+        );
+    }
+
+    if !failed.is_empty() {
+        let days = failed
+            .iter()
+            .map(Day::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let note = if is_timed {
+            ", their timings were not recorded"
+        } else {
+            ""
+        };
+        println!(
+            "\n{ANSI_BOLD}Failed:{ANSI_RESET} {} day(s) ({days}){note}.",
+            failed.len()
+        );
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
@@ -59,15 +99,18 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Path of a day's solution. Solutions that don't exist yet are assumed to be a single file.
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    let layout = BinLayout::detect(day).unwrap_or(BinLayout::File);
+    format!("./{}", layout.path(day))
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{get_path_for_bin, Error, INPUT_MISSING, MEMORY_REPORT};
+    use crate::template::memory::MemoryUsage;
     use crate::template::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -76,11 +119,15 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, returning its stdout lines and whether it exited successfully.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<(Vec<String>, bool), Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok((vec![], true));
         }
 
         let day_padded = day.to_string();
@@ -91,9 +138,11 @@ pub mod child_commands {
         }
 
         if is_timed {
-            // mirror `--time` flag to child invocations.
+            // mirror `--time` flag to child invocations, timing all variants to store the fastest one.
             args.push("--");
             args.push("--time");
+            args.push("--variants");
+            args.push("--report-memory");
         }
 
         // spawn child command with piped stdout/stderr.
@@ -118,24 +167,43 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            // memory reports are only meant to be parsed, their usage was already printed after the timing.
+            if !line.starts_with(MEMORY_REPORT) {
+                println!("{line}");
+            }
             output.push(line);
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        Ok((output, status.success()))
+    }
 
-        Ok(output)
+    /// Whether the solution exited early because its input has not been downloaded.
+    pub fn is_input_missing(output: &[String]) -> bool {
+        output.iter().any(|l| l.starts_with(INPUT_MISSING))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            ..Default::default()
         };
 
+        // NOTE: intermediate output is overwritten with `\r`, only the last segment is the final result.
+        for line in output.iter().filter_map(|l| l.split('\r').next_back()) {
+            if !line.starts_with("Part ") || !line.trim_end().ends_with("✖ error") {
+                continue;
+            }
+
+            if line.starts_with("Part 1:") {
+                timings.failed_parts.push(1);
+            } else if line.starts_with("Part 2:") {
+                timings.failed_parts.push(2);
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
@@ -161,6 +229,14 @@ pub mod child_commands {
                 timings.total_nanos += nanos;
             });
 
+        for (part, memory) in output.iter().filter_map(|l| parse_memory_report(l)) {
+            match part {
+                1 => timings.memory_1 = Some(memory),
+                2 => timings.memory_2 = Some(memory),
+                _ => {}
+            }
+        }
+
         timings
     }
 
@@ -174,7 +250,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -189,6 +265,22 @@ pub mod child_commands {
         Some((str_timing, parsed_timing))
     }
 
+    /// Memory usage of a part with exact byte counts, see `runner::report_memory`.
+    fn parse_memory_report(line: &str) -> Option<(u8, MemoryUsage)> {
+        let mut fields = line.strip_prefix(MEMORY_REPORT)?.split_whitespace();
+        let part = fields.next()?.parse().ok()?;
+        let mut count = || fields.next()?.parse().ok();
+
+        Some((
+            part,
+            MemoryUsage {
+                allocations: count()?,
+                allocated_bytes: count()?,
+                peak_bytes: count()?,
+            },
+        ))
+    }
+
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,7 +297,8 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{is_input_missing, parse_exec_time};
+        use crate::template::memory::MemoryUsage;
 
         use crate::day;
 
@@ -252,6 +345,75 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.failed_parts.is_empty(), true);
+        }
+
+        #[test]
+        fn detects_missing_input() {
+            assert_eq!(
+                is_input_missing(&["input missing — run `cargo download 01`".into()]),
+                true
+            );
+            assert_eq!(
+                is_input_missing(&["Part 1: input missing (1ms @ 1 samples)".into()]),
+                false
+            );
+        }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ error > benching\rPart 1: ✖ error             ".into(),
+                    "  line 1, column 1: expected a number, found \"Part 2: ✖ error\"".into(),
+                    "Part 2: 10 (100ms @ 1 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.failed_parts, vec![1]);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.unwrap(), "100ms");
+        }
+
+        #[test]
+        fn parses_fastest_variant() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: part_one > benching\rPart 1: 233 (43.1µs @ 8804 samples) [held_karp]"
+                        .into(),
+                    "  ✔ part_one   233  39.8ms  922.9× slower".into(),
+                    "  ✔ held_karp  233  43.1µs  fastest".into(),
+                    "".into(),
+                ],
+                day!(9),
+            );
+            assert_approx_eq!(res.total_nanos, 43100_f64);
+            assert_eq!(res.part_1.unwrap(), "43.1µs");
+        }
+
+        #[test]
+        fn parses_memory_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 42 (1.2ms @ 812 samples) (3 allocs, 1.5 KiB, peak 512 B)".into(),
+                    "memory report: 1 3 1537 512".into(),
+                    "Part 2: 1 (10ms @ 100 samples) (0 allocs, 0 B, peak 0 B) [fast]".into(),
+                    "memory report: 2 0 0 0".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "1.2ms");
+            assert_eq!(
+                res.memory_1,
+                Some(MemoryUsage {
+                    allocations: 3,
+                    allocated_bytes: 1537,
+                    peak_bytes: 512
+                })
+            );
+            assert_eq!(res.memory_2, Some(MemoryUsage::default()));
         }
     }
 }
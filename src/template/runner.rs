@@ -1,35 +1,447 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
 use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
+use crate::template::explain::{self, Detail};
+use crate::template::log;
+use crate::template::memory::{self, MemoryUsage};
+use crate::template::params::{self, SolutionParams};
+use crate::template::property::Rng;
+use crate::template::scaling;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, get_generated_marker_path, is_input_generated, Answer, Day, Input, InputSource,
+    ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Printed by a solution when its input has not been downloaded yet.
+pub const INPUT_MISSING: &str = "input missing";
+
+/// Read the input for a day from the source selected on the command-line (see [`InputSource`]).
+/// If the puzzle input is missing or empty (as created by `scaffold`), prints a hint on how to download it and exits.
+pub fn read_input_or_exit(day: Day) -> Input {
+    let args: Vec<String> = env::args().collect();
+    let source = match InputSource::from_args(&args) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match source.read(day) {
+        Ok(input) if source != InputSource::Puzzle || !input.trim().is_empty() => {
+            Input::new(&input)
+        }
+        Ok(_) => {
+            println!("{INPUT_MISSING} — run `cargo download {day}`");
+            process::exit(1);
+        }
+        Err(e) if source == InputSource::Puzzle && e.kind() == io::ErrorKind::NotFound => {
+            println!("{INPUT_MISSING} — run `cargo download {day}`");
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("could not read {source}: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Creates a synthetic input for a day, see the `generate` option of `solution!`.
+///
+/// Generators take a seeded random number generator and the size of the input, whose meaning is up to the day, e.g.
+/// its number of lines. A size of `None` asks for an input about as large as a real one.
+pub type Generator = fn(&mut Rng, Option<usize>) -> String;
+
+/// Parses the value following `name` in the arguments, if present.
+fn parse_arg<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    match args.iter().position(|x| x == name) {
+        Some(index) => args
+            .get(index + 1)
+            .and_then(|value| value.parse().ok())
+            .map(Some)
+            .ok_or_else(|| format!("invalid value for {name}")),
+        None => Ok(None),
+    }
+}
+
+/// With `--generate`, writes an input created by the day's generator to `data/inputs/<day>.txt` and exits.
+/// The seed defaults to the day, so generated inputs are reproducible. Real inputs are only replaced with `--overwrite`.
+pub fn generate_input_if_requested(day: Day, generator: Option<Generator>) {
+    let args: Vec<String> = env::args().collect();
+
+    if !args.iter().any(|x| x == "--generate") {
+        return;
+    }
+
+    let Some(generator) = generator else {
+        eprintln!(
+            "day {day} has no input generator, add one with `generate = <function>` in `solution!`"
+        );
+        process::exit(1);
+    };
+
+    let (seed, size) = match (parse_arg(&args, "--seed"), parse_arg(&args, "--size")) {
+        (Ok(seed), Ok(size)) => (seed.unwrap_or(u64::from(day.into_inner())), size),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let path = PathBuf::from("data")
+        .join("inputs")
+        .join(format!("{day}.txt"));
+
+    let exists = fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0);
+    if exists && !args.iter().any(|x| x == "--overwrite") {
+        eprintln!(
+            "\"{}\" already contains an input, pass --overwrite to replace it",
+            path.display()
+        );
+        process::exit(1);
+    }
+
+    let mut input = generator(&mut Rng::new(seed), size);
+    if !input.ends_with('\n') {
+        input.push('\n');
+    }
+
+    if let Err(e) = fs::write(&path, input) {
+        eprintln!("could not write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+
+    let marker = get_generated_marker_path(day);
+    let size = size.map_or_else(|| "default".into(), |size: usize| size.to_string());
+    if let Err(e) = fs::write(&marker, format!("seed = {seed}\nsize = {size}\n")) {
+        eprintln!("could not write \"{}\": {e}", marker.display());
+        process::exit(1);
+    }
+
+    println!(
+        "Generated input for day {day} with seed {seed} in \"{}\"",
+        path.display()
+    );
+    process::exit(0);
+}
+
+/// With `--part N`, only part `N` runs and `--part 0` runs none, see `cargo time --instructions`.
+fn is_part_selected(part: u8) -> bool {
+    let args: Vec<String> = env::args().collect();
+
+    match parse_arg::<u8>(&args, "--part") {
+        Ok(selected) => selected.is_none_or(|selected| selected == part),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// The outcome of a single solution part.
+pub enum PartOutcome<T> {
+    Answer(T),
+    NoAnswer,
+    Error(String),
+}
+
+/// Return types accepted from solution parts: `Option<T>` for parts that may not have an answer and
+/// `Result<T, E>` for parts that can explain why they failed. `T` can be any [`Answer`].
+pub trait PartResult {
+    type Answer: Answer;
+
+    fn outcome(&self) -> PartOutcome<&Self::Answer>;
+}
+
+impl<T: Answer> PartResult for Option<T> {
+    type Answer = T;
+
+    fn outcome(&self) -> PartOutcome<&T> {
+        match self {
+            Some(answer) => PartOutcome::Answer(answer),
+            None => PartOutcome::NoAnswer,
+        }
+    }
+}
+
+impl<T: Answer, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn outcome(&self) -> PartOutcome<&T> {
+        match self {
+            Ok(answer) => PartOutcome::Answer(answer),
+            Err(err) => PartOutcome::Error(err.to_string()),
+        }
+    }
+}
+
+/// Run a solution part. `func` may take either the normalised [`Input`] or a plain `&str`.
+pub fn run_part<'a, I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: &'a Input,
+    day: Day,
+    part: u8,
+) where
+    &'a Input: Into<I>,
+{
+    let input: I = input.into();
+    run_part_with(|| input, func, day, part);
+}
+
+/// Run a solution part that takes ownership of its input, e.g. to consume or mutate it.
+/// `setup` turns the [`Input`] (or `&str`) into a fresh value for every run. Its cost is not included in the timings.
+pub fn run_part_batched<'a, I: Copy, S, R: PartResult>(
+    setup: impl Fn(I) -> S,
+    func: impl Fn(S) -> R,
+    input: &'a Input,
+    day: Day,
+    part: u8,
+) where
+    &'a Input: Into<I>,
+{
+    let input: I = input.into();
+    run_part_with(|| setup(input), func, day, part);
+}
+
+/// A named implementation of a solution part, see the `variants(N)` option of `solution!`.
+pub struct Variant<'a, S, R> {
+    pub name: &'static str,
+    pub func: Box<dyn Fn(S) -> R + 'a>,
+}
+
+impl<'a, S, R> Variant<'a, S, R> {
+    pub fn new(name: &'static str, func: impl Fn(S) -> R + 'a) -> Self {
+        Self {
+            name,
+            func: Box::new(func),
+        }
+    }
+}
+
+/// Run a solution part that has several implementations. The first variant is the part itself.
+///
+/// Only the first variant runs, unless `--variants` is passed: then all variants run and are compared,
+/// see [`compare_variants`].
+pub fn run_part_variants<'a, I: Copy, S, R: PartResult>(
+    setup: impl Fn(I) -> S,
+    variants: Vec<Variant<S, R>>,
+    input: &'a Input,
+    day: Day,
+    part: u8,
+) where
+    &'a Input: Into<I>,
+{
+    let input: I = input.into();
+
+    if env::args().any(|x| x == "--variants") {
+        compare_variants(|| setup(input), &variants, day, part);
+    } else {
+        run_part_with(|| setup(input), &variants[0].func, day, part);
+    }
+}
+
+fn run_part_with<S, R: PartResult>(
+    setup: impl Fn() -> S,
+    func: impl Fn(S) -> R,
+    day: Day,
+    part: u8,
+) {
+    if !is_part_selected(part) {
+        return;
+    }
+
+    let part_str = format!("Part {part}");
+    log::set_part(part);
+
+    if let Some(size) = scaling::current_size() {
+        print!("{part_str} @ n = {size}");
+        let _ = stdout().flush();
+
+        let duration = measure_scaling(setup, func);
+        println!("\r{part_str} @ n = {size}: {duration:.1?}           ");
+        scaling::record(part, duration);
+        return;
+    }
+
+    let is_timed = env::args().any(|x| x == "--time");
+    let (result, duration, samples, base_time, memory) =
+        run_timed(&setup, &func, is_timed, |result| {
+            print_result(&result.outcome(), &part_str, "");
+        });
+
+    let outcome = result.outcome();
+    print_result(
+        &outcome,
+        &part_str,
+        &format!(
+            "{}{}",
+            format_duration(&duration, samples),
+            format_memory(memory)
+        ),
+    );
+    report_memory(part, memory);
+
+    if samples > 1 {
+        warn_about_warm_speedup(&part_str, base_time, duration);
+    }
+
+    if env::args().any(|x| x == "--explain") {
+        let (_, details) = log::suspended(|| explain::capture(|| func(setup())));
+        print_details(&details);
+    }
+
+    if let PartOutcome::Answer(answer) = outcome {
+        submit_result(answer, day, part);
+    }
+}
+
+/// Identifies an outcome when comparing variants. Variants agree if they would submit the same answer,
+/// or render the same one if it can't be submitted.
+fn outcome_key<R: PartResult>(result: &R) -> String {
+    match result.outcome() {
+        PartOutcome::Answer(answer) => answer.submission().unwrap_or_else(|| answer.render()),
+        PartOutcome::NoAnswer => "✖".into(),
+        PartOutcome::Error(err) => format!("✖ error: {err}"),
+    }
+}
+
+/// Runs and benches every variant of a part. The `Part N:` line reports the fastest variant that
+/// agrees with the first one, followed by a table comparing all variants. When variants disagree, the process exits
+/// with an error, so that `cargo time` does not store the timing.
+fn compare_variants<S, R: PartResult>(
+    setup: impl Fn() -> S,
+    variants: &[Variant<S, R>],
+    day: Day,
+    part: u8,
+) {
+    if !is_part_selected(part) {
+        return;
+    }
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    log::set_part(part);
+
+    let runs: Vec<(R, Duration, u128, Duration, Option<MemoryUsage>)> = variants
+        .iter()
+        .map(|variant| {
+            print!("\r{part_str}: {ANSI_ITALIC}{}{ANSI_RESET}", variant.name);
+            let _ = stdout().flush();
+            run_timed(&setup, &variant.func, true, |_| {})
+        })
+        .collect();
+
+    let keys: Vec<String> = runs
+        .iter()
+        .map(|(result, ..)| outcome_key(result))
+        .collect();
+    let agrees = |index: usize| keys[index] == keys[0];
+
+    let fastest = (0..runs.len())
+        .filter(|&index| agrees(index))
+        .min_by_key(|&index| runs[index].1)
+        .unwrap_or(0);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, base_time, memory) = &runs[fastest];
+    print!("\r");
+    print_result(
+        &result.outcome(),
+        &part_str,
+        &format!(
+            "{}{} [{}]",
+            format_duration(duration, *samples),
+            format_memory(*memory),
+            variants[fastest].name
+        ),
+    );
+    report_memory(part, *memory);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+    for (index, variant) in variants.iter().enumerate() {
+        let duration = runs[index].1;
+        let status = if agrees(index) { "✔" } else { "✖" };
+        let relative = if !agrees(index) {
+            "disagrees".to_string()
+        } else if index == fastest {
+            "fastest".to_string()
+        } else {
+            format!(
+                "{:.1}× slower",
+                duration.as_secs_f64() / runs[fastest].1.as_secs_f64().max(f64::EPSILON)
+            )
+        };
+        let answer = keys[index].lines().next().unwrap_or_default();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        println!(
+            "  {status} {:<width$}  {ANSI_BOLD}{answer}{ANSI_RESET}  {duration:.1?}  {ANSI_ITALIC}{relative}{ANSI_RESET}",
+            variant.name
+        );
+    }
+
+    if *samples > 1 {
+        warn_about_warm_speedup(&part_str, *base_time, *duration);
+    }
+
+    if (0..runs.len()).any(|index| !agrees(index)) {
+        eprintln!(
+            "{part_str}: ✖ variants disagree with `{}`",
+            variants[0].name
+        );
+        process::exit(1);
+    }
+
+    // the part itself is explained, variants such as a faster algorithm may not record any details.
+    if env::args().any(|x| x == "--explain") {
+        let func = &variants[0].func;
+        let (_, details) = log::suspended(|| explain::capture(|| func(setup())));
+        print_details(&details);
+    }
+
+    if let PartOutcome::Answer(answer) = result.outcome() {
+        submit_result(answer, day, part);
+    }
+}
+
+fn print_details(details: &[Detail]) {
+    if details.is_empty() {
+        println!("  {ANSI_ITALIC}no details{ANSI_RESET}");
+    }
+
+    for detail in details {
+        let mut lines = detail.value.lines();
+        println!(
+            "  {ANSI_BOLD}{}{ANSI_RESET}: {}",
+            detail.key,
+            lines.next().unwrap_or_default()
+        );
+        for line in lines {
+            println!("    {line}");
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
+/// Run a solution part. The behavior differs depending on whether we are benchmarking:
+///  1. usually, the function is executed once.
+///  2. with `is_timed`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The allocations are counted on an extra run after the first execution, see [`memory`].
+///
+/// `setup` produces the input for each execution and is excluded from the measured time.
+/// Also returns the time of the first execution, so it can be compared with the benched time.
+fn run_timed<S, T>(
+    setup: impl Fn() -> S,
+    func: impl Fn(S) -> T,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Duration, Option<MemoryUsage>) {
+    let input = setup();
+
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,30 +453,87 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    // NOTE: allocations are counted on a separate run, so that counting does not slow down the timed first run.
+    // That run is warm: caches filled by the first run are not counted again, unless `reset` clears them in `setup`.
+    let memory = if is_timed {
+        let input = setup();
+        memory::measure(|| func(input)).1
+    } else {
+        None
+    };
+
+    let run = if is_timed {
+        log::suspended(|| bench(setup, &func, &base_time, BENCH_TIME))
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, base_time, memory)
+}
+
+/// The first run of a solution is expected to be somewhat slower than later ones (cold caches, lazy initialisation).
+/// If it is slower by this factor or more, the later runs likely re-used results of the first one.
+const WARM_SPEEDUP_THRESHOLD: f64 = 10.0;
+
+/// First runs faster than this are dominated by noise and never flagged.
+const WARM_SPEEDUP_MIN_BASE_TIME: Duration = Duration::from_micros(100);
+
+/// How much faster the benched runs were than the first one, if that is suspiciously fast.
+fn warm_speedup(base_time: Duration, bench_time: Duration) -> Option<f64> {
+    if base_time < WARM_SPEEDUP_MIN_BASE_TIME {
+        return None;
+    }
+
+    let speedup = base_time.as_secs_f64() / bench_time.as_secs_f64().max(f64::EPSILON);
+    (speedup >= WARM_SPEEDUP_THRESHOLD).then_some(speedup)
+}
+
+fn warn_about_warm_speedup(part_str: &str, base_time: Duration, duration: Duration) {
+    if let Some(speedup) = warm_speedup(base_time, duration) {
+        eprintln!(
+            "{part_str}: ⚠ warm runs were {speedup:.0}× faster than the first run ({base_time:.1?}). \
+            The solution may be caching results across runs, add a `reset` function to `solution!` to clear them."
+        );
+    }
+}
+
+/// Times a part for `--scaling`. Runs on large inputs are only benched if they are fast enough.
+fn measure_scaling<S, T>(setup: impl Fn() -> S, func: impl Fn(S) -> T) -> Duration {
+    let (_, base_time, ..) = run_timed(&setup, &func, false, |_| {});
+
+    if base_time < scaling::BENCH_LIMIT {
+        log::suspended(|| bench(setup, func, &base_time, scaling::BENCH_TIME)).0
+    } else {
+        base_time
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// How long a part is benched for, unless that takes less than 10 samples.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+
+fn bench<S, T>(
+    setup: impl Fn() -> S,
+    func: impl Fn(S) -> T,
+    base_time: &Duration,
+    bench_time: Duration,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (bench_time.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
+        let input = setup();
         let timer = Instant::now();
-        black_box(func(black_box(input)));
+        let result = func(black_box(input));
         timers.push(timer.elapsed());
+        // Dropping the result is not part of the measurement either.
+        black_box(result);
     }
 
     (
@@ -90,22 +559,50 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Memory usage follows the duration in parentheses, e.g. `(1.2ms @ 812 samples) (3 allocs, 1.5 KiB, peak 1.0 KiB)`.
+fn format_memory(memory: Option<MemoryUsage>) -> String {
+    memory.map_or_else(String::new, |memory| format!(" ({memory})"))
+}
+
+/// Prefix of the line printed by [`report_memory`].
+pub const MEMORY_REPORT: &str = "memory report:";
+
+/// With `--report-memory`, prints the memory usage of a part with exact byte counts, e.g.
+/// `memory report: 1 3 1536 512` for part 1 with 3 allocations of 1536 bytes and a peak of 512 bytes.
+/// `cargo time` stores these instead of the rounded sizes printed after the timing.
+fn report_memory(part: u8, memory: Option<MemoryUsage>) {
+    if let Some(memory) = memory.filter(|_| env::args().any(|x| x == "--report-memory")) {
+        println!(
+            "{MEMORY_REPORT} {part} {} {} {}",
+            memory.allocations, memory.allocated_bytes, memory.peak_bytes
+        );
+    }
+}
+
+fn print_result<T: Answer>(outcome: &PartOutcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+    match outcome {
+        PartOutcome::Answer(result) => {
+            let rendered = result.render();
+
+            if rendered.contains('\n') {
+                // e.g. a grid showing letters: print what would be submitted, followed by the picture.
+                let str = match result.submission() {
+                    Some(submission) => {
+                        format!("{part}: {ANSI_BOLD}{submission}{ANSI_RESET} ▼{duration_str}")
+                    }
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
                     print!("\r");
                     println!("{str}");
-                    println!("{result}");
+                    println!("{rendered}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{rendered}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -114,7 +611,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        PartOutcome::NoAnswer => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,14 +619,25 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        PartOutcome::Error(err) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ error");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ error             ");
+                for line in err.lines() {
+                    println!("  {ANSI_ITALIC}{line}{ANSI_RESET}");
+                }
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+fn submit_result<T: Answer>(
+    result: &T,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -155,11 +663,88 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    match InputSource::from_args(&args) {
+        Ok(InputSource::Puzzle) => {}
+        Ok(source) => {
+            eprintln!("Not submitting result computed from {source}.");
+            return None;
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            return None;
+        }
+    }
+
+    if is_input_generated(day) {
+        eprintln!("Not submitting result computed from a generated input, run `cargo download {day}` to replace it.");
+        return None;
+    }
+
+    if !params::get_param_args(&args).is_empty() {
+        eprintln!("Not submitting result computed with overridden parameters (--param).");
+        return None;
+    }
+
+    let Some(submission) = result
+        .submission()
+        .filter(|submission| !submission.contains('\n'))
+    else {
+        eprintln!("Not submitting result that is not a single line, e.g. a grid that doesn't show letters.");
+        return None;
+    };
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &submission))
+}
+
+/// Set up logging for this run (see [`log::init`]), exiting with an error message if the log file can't be created.
+pub fn init_logging_or_exit(day: Day) {
+    if let Err(e) = log::init(day) {
+        eprintln!("could not create log file: {e}");
+        process::exit(1);
+    }
+}
+
+/// Resolve the parameters for this run (see [`params::from_env`]), exiting with an error message if they are invalid.
+pub fn read_params_or_exit<P: SolutionParams>(day: Day) -> P {
+    params::from_env(day).unwrap_or_else(|e| {
+        eprintln!("invalid parameters: {e}");
+        process::exit(1);
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{outcome_key, warm_speedup};
+    use std::time::Duration;
+
+    #[test]
+    fn compares_outcomes_by_submission() {
+        assert_eq!(outcome_key(&Some(605)), outcome_key(&Ok::<_, String>(605)));
+        assert_ne!(outcome_key(&Some(605)), outcome_key(&Some(606)));
+        assert_ne!(outcome_key(&None::<u32>), outcome_key(&Some(0)));
+    }
+
+    #[test]
+    fn flags_suspiciously_fast_warm_runs() {
+        let speedup = warm_speedup(Duration::from_millis(2), Duration::from_nanos(400));
+        assert_eq!(speedup.map(f64::round), Some(5000.0));
+    }
+
+    #[test]
+    fn ignores_regular_warm_runs() {
+        assert_eq!(
+            warm_speedup(Duration::from_millis(2), Duration::from_millis(1)),
+            None
+        );
+        assert_eq!(
+            warm_speedup(Duration::from_micros(50), Duration::from_nanos(10)),
+            None
+        );
+    }
 }
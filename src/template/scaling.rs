@@ -0,0 +1,397 @@
+/// Empirical complexity analysis of solutions, see `cargo time <day> --scaling`.
+///
+/// Runs every part on inputs of growing size and fits the timings against common complexity classes. Inputs are created
+/// by the day's generator (see the `generate` option of `solution!`), or are prefixes of the puzzle input for days
+/// without one. The fit is done on a log scale, so large and small sizes weigh the same.
+use std::env;
+use std::f64::consts::LN_2;
+use std::fmt::Display;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::template::property::Rng;
+use crate::template::runner::{read_input_or_exit, Generator};
+use crate::template::{Day, Input, ANSI_BOLD, ANSI_RESET};
+
+/// Runs faster than this are benched, slower ones are timed once so that large inputs don't take forever.
+pub const BENCH_LIMIT: Duration = Duration::from_millis(100);
+
+/// How long a part is benched for at each size.
+pub const BENCH_TIME: Duration = Duration::from_millis(200);
+
+/// Larger sizes are skipped once a part took longer than this.
+const TIME_LIMIT: Duration = Duration::from_secs(1);
+
+/// Fewer sizes than this can't tell complexity classes apart.
+const MIN_SIZES: usize = 3;
+
+const CHART_WIDTH: usize = 30;
+
+const NOT_SCALING: usize = usize::MAX;
+
+static SIZE: AtomicUsize = AtomicUsize::new(NOT_SCALING);
+static SAMPLES: Mutex<Vec<Sample>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    part: u8,
+    size: usize,
+    duration: Duration,
+}
+
+/// Sizes for days that have a generator, but don't declare `scaling = <sizes>` in `solution!`.
+fn default_sizes() -> Vec<usize> {
+    (4..=20).map(|exponent| 1 << exponent).collect()
+}
+
+/// The size of the current input while running with `--scaling`.
+pub fn current_size() -> Option<usize> {
+    match SIZE.load(Ordering::Relaxed) {
+        NOT_SCALING => None,
+        size => Some(size),
+    }
+}
+
+/// Records the time a part took on the current input. Called by the runner instead of printing a result.
+pub fn record(part: u8, duration: Duration) {
+    let Some(size) = current_size() else {
+        return;
+    };
+
+    if let Ok(mut samples) = SAMPLES.lock() {
+        samples.push(Sample {
+            part,
+            size,
+            duration,
+        });
+    }
+}
+
+/// With `--scaling`, runs the parts on inputs of growing size, reports how their timings scale and exits.
+///
+/// `sizes` are passed to the generator, or are numbers of lines of the puzzle input for days without a generator.
+/// `run` runs all parts of the solution on an input.
+pub fn run_if_requested(
+    day: Day,
+    generator: Option<Generator>,
+    sizes: Option<Vec<usize>>,
+    run: impl Fn(&Input),
+) {
+    if !env::args().any(|x| x == "--scaling") {
+        return;
+    }
+
+    let (sizes, create_input): (Vec<usize>, Box<dyn Fn(usize) -> Input>) = match generator {
+        Some(generator) => {
+            println!("Scaling of day {day} on generated inputs:");
+            let seed = u64::from(day.into_inner());
+            (
+                sizes.unwrap_or_else(default_sizes),
+                Box::new(move |size| Input::new(&generator(&mut Rng::new(seed), Some(size)))),
+            )
+        }
+        None => {
+            let input = read_input_or_exit(day);
+            let lines: Vec<String> = input.lines().map(String::from).collect();
+            let sizes = sizes.unwrap_or_else(|| prefix_sizes(lines.len()));
+
+            if sizes.len() < MIN_SIZES {
+                eprintln!(
+                    "day {day} needs an input generator to measure its scaling, add one with `generate = <function>` \
+                    in `solution!`"
+                );
+                process::exit(1);
+            }
+
+            println!("Scaling of day {day} on the first lines of the puzzle input:");
+            (
+                sizes,
+                Box::new(move |size| Input::new(&lines[..size.min(lines.len())].join("\n"))),
+            )
+        }
+    };
+
+    for size in sizes {
+        SIZE.store(size, Ordering::Relaxed);
+        run(&create_input(size));
+        SIZE.store(NOT_SCALING, Ordering::Relaxed);
+
+        let slowest = samples()
+            .iter()
+            .filter(|sample| sample.size == size)
+            .map(|sample| sample.duration)
+            .max();
+
+        if slowest.is_some_and(|slowest| slowest > TIME_LIMIT) {
+            println!("Skipping larger sizes, n = {size} took longer than {TIME_LIMIT:?}.");
+            break;
+        }
+    }
+
+    let samples = samples();
+    let mut parts: Vec<u8> = samples.iter().map(|sample| sample.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    for part in parts {
+        let points: Vec<(usize, Duration)> = samples
+            .iter()
+            .filter(|sample| sample.part == part && sample.size > 0)
+            .map(|sample| (sample.size, sample.duration))
+            .collect();
+
+        println!();
+        if points.len() < MIN_SIZES {
+            println!(
+                "Part {part}: ✖ measured {} sizes, at least {MIN_SIZES} are needed to fit a complexity",
+                points.len()
+            );
+            continue;
+        }
+
+        let fits = fit(&points);
+        println!(
+            "Part {part}: {ANSI_BOLD}{}{ANSI_RESET} fits best, followed by {}",
+            fits[0].complexity, fits[1].complexity
+        );
+        println!("{}", render_chart(&points, &fits[0]));
+    }
+
+    process::exit(0);
+}
+
+fn samples() -> Vec<Sample> {
+    SAMPLES
+        .lock()
+        .map(|samples| samples.clone())
+        .unwrap_or_default()
+}
+
+/// Line counts of prefixes of an input with `lines` lines, doubling up to the whole input.
+fn prefix_sizes(lines: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = (0..8)
+        .map(|halvings| lines >> halvings)
+        .filter(|&size| size > 0)
+        .collect();
+    sizes.dedup();
+    sizes.reverse();
+    sizes
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A complexity class that timings are fitted against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Exponential,
+    Factorial,
+}
+
+impl Complexity {
+    const ALL: [Self; 6] = [
+        Self::Constant,
+        Self::Linear,
+        Self::Linearithmic,
+        Self::Quadratic,
+        Self::Exponential,
+        Self::Factorial,
+    ];
+
+    /// The natural logarithm of the growth at size `n`, which keeps `2ⁿ` and `n!` from overflowing.
+    #[allow(clippy::cast_precision_loss)]
+    fn ln_growth(self, n: usize) -> f64 {
+        let x = n as f64;
+
+        match self {
+            Self::Constant => 0.0,
+            Self::Linear => x.ln(),
+            Self::Linearithmic => x.ln() + x.log2().max(1.0).ln(),
+            Self::Quadratic => 2.0 * x.ln(),
+            Self::Exponential => x * LN_2,
+            Self::Factorial => (2..=n).map(|k| (k as f64).ln()).sum(),
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Self::Constant => "O(1)",
+            Self::Linear => "O(n)",
+            Self::Linearithmic => "O(n log n)",
+            Self::Quadratic => "O(n²)",
+            Self::Exponential => "O(2ⁿ)",
+            Self::Factorial => "O(n!)",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// Timings fitted against a complexity class as `time = c * growth(n)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Fit {
+    pub complexity: Complexity,
+    /// The natural logarithm of `c`, in nanoseconds.
+    ln_scale: f64,
+    /// The root mean square of the residuals on a log scale. Lower is better.
+    pub error: f64,
+}
+
+impl Fit {
+    /// The fitted time at size `n`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn predict(&self, n: usize) -> Duration {
+        let nanos = (self.ln_scale + self.complexity.ln_growth(n)).exp().round();
+        Duration::from_nanos(nanos.min(u64::MAX as f64) as u64)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ln_nanos(duration: Duration) -> f64 {
+    (duration.as_nanos() as f64).max(1.0).ln()
+}
+
+/// Fits timings at sizes `n >= 1` against all complexity classes, best fit first.
+#[allow(clippy::cast_precision_loss)]
+pub fn fit(points: &[(usize, Duration)]) -> Vec<Fit> {
+    let mut fits: Vec<Fit> = Complexity::ALL
+        .iter()
+        .map(|&complexity| {
+            let offsets: Vec<f64> = points
+                .iter()
+                .map(|&(n, duration)| ln_nanos(duration) - complexity.ln_growth(n))
+                .collect();
+
+            let ln_scale = offsets.iter().sum::<f64>() / offsets.len() as f64;
+            let error = (offsets
+                .iter()
+                .map(|offset| (offset - ln_scale).powi(2))
+                .sum::<f64>()
+                / offsets.len() as f64)
+                .sqrt();
+
+            Fit {
+                complexity,
+                ln_scale,
+                error,
+            }
+        })
+        .collect();
+
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// A bar per size, on a log scale, with the measured and fitted timings.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn render_chart(points: &[(usize, Duration)], fit: &Fit) -> String {
+    let ln_times: Vec<f64> = points
+        .iter()
+        .map(|&(_, duration)| ln_nanos(duration))
+        .collect();
+    let min = ln_times.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ln_times.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let size_width = points
+        .iter()
+        .map(|(n, _)| n.to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    points
+        .iter()
+        .zip(&ln_times)
+        .map(|(&(n, duration), &ln_time)| {
+            let ratio = if max > min {
+                (ln_time - min) / (max - min)
+            } else {
+                1.0
+            };
+            let bar = "█".repeat(1 + (ratio * (CHART_WIDTH - 1) as f64).round() as usize);
+            let measured = format!("{duration:.1?}");
+            let fitted = format!("{:.1?}", fit.predict(n));
+
+            format!("  n = {n:>size_width$}  {bar:<CHART_WIDTH$}  {measured:>9}  (fit {fitted})")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{current_size, fit, prefix_sizes, render_chart, Complexity};
+
+    fn points(
+        sizes: impl Iterator<Item = usize>,
+        nanos: impl Fn(u64) -> u64,
+    ) -> Vec<(usize, Duration)> {
+        sizes
+            .map(|n| (n, Duration::from_nanos(nanos(n as u64))))
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let best = |points: &[(usize, Duration)]| fit(points)[0].complexity;
+
+        assert_eq!(
+            best(&points((4..=12).map(|e| 1 << e), |_| 500)),
+            Complexity::Constant
+        );
+        assert_eq!(
+            best(&points((4..=12).map(|e| 1 << e), |n| 30 * n)),
+            Complexity::Linear
+        );
+        assert_eq!(
+            best(&points((4..=12).map(|e| 1 << e), |n| 7 * n * n)),
+            Complexity::Quadratic
+        );
+        assert_eq!(
+            best(&points(2..=10, |n| 100 * (1..=n).product::<u64>())),
+            Complexity::Factorial
+        );
+        assert_eq!(best(&points(4..=20, |n| 50 << n)), Complexity::Exponential);
+    }
+
+    #[test]
+    fn predicts_fitted_timings() {
+        let fits = fit(&points((4..=10).map(|e| 1 << e), |n| 10 * n));
+        assert_eq!(fits[0].predict(2048), Duration::from_nanos(20480));
+    }
+
+    #[test]
+    fn renders_chart_on_log_scale() {
+        let points = points([1, 10, 100].into_iter(), |n| 10 * n);
+        let chart = render_chart(&points, &fit(&points)[0]);
+        let bars: Vec<usize> = chart
+            .lines()
+            .map(|line| line.matches('█').count())
+            .collect();
+
+        assert_eq!(bars, vec![1, 16, 30]);
+        assert!(chart.starts_with("  n =   1  █ "));
+        assert!(chart.ends_with("1.0µs  (fit 1.0µs)"));
+    }
+
+    #[test]
+    fn doubles_prefixes_up_to_whole_input() {
+        assert_eq!(prefix_sizes(300), vec![2, 4, 9, 18, 37, 75, 150, 300]);
+        assert_eq!(prefix_sizes(3), vec![1, 3]);
+    }
+
+    #[test]
+    fn is_not_scaling_by_default() {
+        assert_eq!(current_size(), None);
+    }
+}
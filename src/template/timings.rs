@@ -1,17 +1,38 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::cachegrind::InstructionCounts;
+use crate::template::memory::MemoryUsage;
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Parts that returned an error instead of an answer.
+    pub failed_parts: Vec<u8>,
+    /// Heap usage of the parts, if it was counted.
+    pub memory_1: Option<MemoryUsage>,
+    pub memory_2: Option<MemoryUsage>,
+    /// Instruction counts of the parts, if they were measured with `cargo time --instructions`.
+    pub instructions_1: Option<InstructionCounts>,
+    pub instructions_2: Option<InstructionCounts>,
+}
+
+impl Timing {
+    pub fn is_failed(&self, part: u8) -> bool {
+        self.failed_parts.contains(&part)
+    }
+
+    /// Whether the day has wall-clock timings. Days only measured with `cargo time --instructions` have none.
+    pub fn is_timed(&self) -> bool {
+        self.part_1.is_some() || self.part_2.is_some() || !self.failed_parts.is_empty()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -38,11 +59,17 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Instruction counts are measured separately, so stored ones are kept if `other` has none.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.instructions_1 = timing.instructions_1.or(stored.instructions_1);
+                timing.instructions_2 = timing.instructions_2.or(stored.instructions_2);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,10 +78,26 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
+    /// Remove the timings of a day, e.g. after its solution has been archived.
+    pub fn without(&self, day: Day) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|timing| timing.day != day)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn has_day(&self, day: Day) -> bool {
+        self.data.iter().any(|t| t.day == day)
+    }
+
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
@@ -133,10 +176,139 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "failed_parts".into(),
+            JsonValue::Array(
+                value
+                    .failed_parts
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
+        );
+
+        map.insert("memory_1".into(), memory_to_json(value.memory_1));
+        map.insert("memory_2".into(), memory_to_json(value.memory_2));
+        map.insert(
+            "instructions_1".into(),
+            instructions_to_json(value.instructions_1),
+        );
+        map.insert(
+            "instructions_2".into(),
+            instructions_to_json(value.instructions_2),
+        );
+
         JsonValue::Object(map)
     }
 }
 
+#[allow(clippy::cast_precision_loss)]
+fn memory_to_json(memory: Option<MemoryUsage>) -> JsonValue {
+    let Some(memory) = memory else {
+        return JsonValue::Null;
+    };
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "allocations".into(),
+        JsonValue::Number(memory.allocations as f64),
+    );
+    map.insert(
+        "allocated_bytes".into(),
+        JsonValue::Number(memory.allocated_bytes as f64),
+    );
+    map.insert(
+        "peak_bytes".into(),
+        JsonValue::Number(memory.peak_bytes as f64),
+    );
+    JsonValue::Object(map)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn memory_from_json(value: Option<&JsonValue>, key: &str) -> Result<Option<MemoryUsage>, String> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Ok(None);
+    };
+
+    let field = |name: &str| {
+        value
+            .get::<HashMap<String, JsonValue>>()?
+            .get(name)?
+            .get::<f64>()
+            .map(|x| *x as u64)
+    };
+
+    match (
+        field("allocations"),
+        field("allocated_bytes"),
+        field("peak_bytes"),
+    ) {
+        (Some(allocations), Some(allocated_bytes), Some(peak_bytes)) => Ok(Some(MemoryUsage {
+            allocations,
+            allocated_bytes,
+            peak_bytes,
+        })),
+        _ => Err(format!(
+            "Expected timing.{key} to be null or a memory usage object."
+        )),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn instructions_to_json(counts: Option<InstructionCounts>) -> JsonValue {
+    let Some(counts) = counts else {
+        return JsonValue::Null;
+    };
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert(
+        "instructions".into(),
+        JsonValue::Number(counts.instructions as f64),
+    );
+    map.insert(
+        "l1_misses".into(),
+        JsonValue::Number(counts.l1_misses as f64),
+    );
+    map.insert(
+        "ll_misses".into(),
+        JsonValue::Number(counts.ll_misses as f64),
+    );
+    JsonValue::Object(map)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn instructions_from_json(
+    value: Option<&JsonValue>,
+    key: &str,
+) -> Result<Option<InstructionCounts>, String> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Ok(None);
+    };
+
+    let field = |name: &str| {
+        value
+            .get::<HashMap<String, JsonValue>>()?
+            .get(name)?
+            .get::<f64>()
+            .map(|x| *x as u64)
+    };
+
+    match (
+        field("instructions"),
+        field("l1_misses"),
+        field("ll_misses"),
+    ) {
+        (Some(instructions), Some(l1_misses), Some(ll_misses)) => Ok(Some(InstructionCounts {
+            instructions,
+            l1_misses,
+            ll_misses,
+        })),
+        _ => Err(format!(
+            "Expected timing.{key} to be null or an instruction counts object."
+        )),
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -166,11 +338,38 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: `failed_parts` is optional, timings stored before it was introduced do not have it.
+        let failed_parts = match json.get("failed_parts") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    parts
+                        .iter()
+                        .map(|part| part.get::<f64>().map(|x| *x as u8))
+                        .collect::<Option<Vec<u8>>>()
+                })
+                .ok_or("Expected timing.failed_parts to be an array of numbers.")?,
+        };
+
+        // NOTE: memory usage is optional as well, it is only counted in benchmark mode.
+        let memory_1 = memory_from_json(json.get("memory_1"), "memory_1")?;
+        let memory_2 = memory_from_json(json.get("memory_2"), "memory_2")?;
+
+        // NOTE: instruction counts are optional too, they require valgrind.
+        let instructions_1 = instructions_from_json(json.get("instructions_1"), "instructions_1")?;
+        let instructions_2 = instructions_from_json(json.get("instructions_2"), "instructions_2")?;
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            failed_parts,
+            memory_1,
+            memory_2,
+            instructions_1,
+            instructions_2,
         })
     }
 }
@@ -191,25 +390,30 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{cachegrind::InstructionCounts, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -221,6 +425,33 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.failed_parts, vec![]);
+        }
+
+        #[test]
+        fn handles_failed_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": "1ms", "total_nanos": 1000000, "failed_parts": [1] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.failed_parts, vec![1]);
+            assert_eq!(timing.is_failed(1), true);
+            assert_eq!(timing.is_failed(2), false);
+        }
+
+        #[test]
+        fn handles_instruction_counts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "instructions_1": { "instructions": 1500, "l1_misses": 20, "ll_misses": 5 }, "instructions_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.instructions_1,
+                Some(InstructionCounts {
+                    instructions: 1500,
+                    l1_misses: 20,
+                    ll_misses: 5,
+                })
+            );
+            assert_eq!(timing.instructions_2, None);
         }
 
         #[test]
@@ -282,10 +513,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -294,12 +526,12 @@ mod tests {
                 data: vec![Timing {
                     day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,20 +539,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                cachegrind::InstructionCounts,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -331,9 +564,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +582,7 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +594,26 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_instruction_counts() {
+            let counts = InstructionCounts {
+                instructions: 1000,
+                l1_misses: 10,
+                ll_misses: 1,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.data[1].instructions_1 = Some(counts);
+
+            let mut other = get_mock_timings().without(day!(1)).without(day!(4));
+            other.data[0].total_nanos = 0_f64;
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].instructions_1, Some(counts));
+            assert_eq!(merged.data[1].instructions_2, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -381,4 +630,25 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
     }
+
+    mod without {
+        use crate::day;
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn removes_day() {
+            let timings = get_mock_timings().without(day!(2));
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].day, day!(1));
+            assert_eq!(timings.data[1].day, day!(4));
+            assert!(!timings.has_day(day!(2)));
+        }
+
+        #[test]
+        fn ignores_missing_day() {
+            let timings = get_mock_timings().without(day!(3));
+            assert_eq!(timings.data.len(), 3);
+        }
+    }
 }